@@ -0,0 +1,101 @@
+//! A naive bot: shoots the nearest enemy, releases the ball as soon as it's captured
+//! and spins counter-clockwise to reload once it runs out of ammo.
+
+use bevy::prelude::*;
+use leafwing_input_manager::{plugin::InputManagerSystem, prelude::*};
+
+use crate::{
+    game::{
+        input::{AimDirection, PlayerAction},
+        spawn::{
+            enemy::{Enemy, Shielded},
+            paddle::{PaddleAmmo, PaddleMode},
+        },
+    },
+    screen::Screen,
+    AppSet, GAME_SIZE,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<BotIntent>()
+        .add_systems(OnEnter(Screen::Game), reset_intent)
+        .add_systems(
+            PreUpdate,
+            press_actions
+                .in_set(InputManagerSystem::ManualControl)
+                .run_if(in_state(Screen::Game)),
+        )
+        .add_systems(
+            Update,
+            think
+                .in_set(AppSet::ProcessInput)
+                .after(update_aim_direction)
+                .run_if(in_state(Screen::Game)),
+        );
+}
+
+/// Start reloading when the ammo runs out & keep at it until there's this much.
+const RELOAD_TARGET: usize = 12;
+/// Max angle between the barrel & the target to pull the trigger.
+const SHOOT_ANGLE_DEG: f32 = 8.;
+
+#[derive(Resource, Debug, Default)]
+struct BotIntent {
+    shoot: bool,
+    toggle_paddle_mode: bool,
+    reloading: bool,
+}
+
+fn reset_intent(mut intent: ResMut<BotIntent>) {
+    *intent = BotIntent::default();
+}
+
+fn think(
+    mut intent: ResMut<BotIntent>,
+    mut aim_dir: ResMut<AimDirection>,
+    paddle_q: Query<(&GlobalTransform, &PaddleMode, &PaddleAmmo)>,
+    enemy_q: Query<(&GlobalTransform, Option<&Shielded>), With<Enemy>>,
+) {
+    let Some((paddle_t, paddle_mode, ammo)) = paddle_q.iter().next() else {
+        return;
+    };
+
+    let paddle_dir = paddle_t.translation().truncate().normalize_or(Vec2::X);
+    let target = enemy_q
+        .iter()
+        // shielded enemies can only be killed by the ball
+        .filter(|(t, shielded)| {
+            shielded.is_none() && t.translation().abs().max_element() < GAME_SIZE / 2.
+        })
+        .map(|(t, _)| t.translation().truncate())
+        .min_by(|a, b| a.length_squared().total_cmp(&b.length_squared()));
+
+    if ammo.ammo() == 0 {
+        intent.reloading = true;
+    } else if ammo.ammo() >= RELOAD_TARGET {
+        intent.reloading = false;
+    }
+
+    intent.toggle_paddle_mode = matches!(paddle_mode, PaddleMode::Captured { .. });
+    intent.shoot = false;
+
+    match target {
+        Some(target) if !intent.reloading => {
+            aim_dir.0 = target.normalize_or(paddle_dir);
+            intent.shoot = paddle_dir.angle_between(aim_dir.0).abs() < SHOOT_ANGLE_DEG.to_radians();
+        }
+        _ => {
+            // keep aiming ahead of the paddle to spin it CCW
+            aim_dir.0 = Rot2::degrees(90.) * paddle_dir;
+        }
+    }
+}
+
+fn press_actions(intent: Res<BotIntent>, mut action_state: ResMut<ActionState<PlayerAction>>) {
+    if intent.shoot {
+        action_state.press(&PlayerAction::Shoot);
+    }
+    if intent.toggle_paddle_mode {
+        action_state.press(&PlayerAction::TogglePaddleMode);
+    }
+}
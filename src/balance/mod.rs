@@ -0,0 +1,135 @@
+//! Headless batch runs played by a bot, used to compare spawn table changes with data.
+//! See `src/bin/balance.rs` for the command-line entry point.
+
+mod bot;
+mod report;
+
+use std::{path::PathBuf, time::Duration};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+use crate::{
+    game::{
+        rng::{GameRng, RunSeed},
+        score::Score,
+        stats::RunStats,
+    },
+    screen::{NextTransitionedState, Screen},
+};
+
+use report::RunReport;
+
+pub use report::ReportFormat;
+
+/// Simulated time of a single frame at 1x speed.
+const FRAME_DURATION_S: f32 = 1. / 60.;
+
+pub struct BalancePlugin(pub BalanceConfig);
+
+#[derive(Resource, Debug, Clone)]
+pub struct BalanceConfig {
+    /// Number of runs to play.
+    pub runs: usize,
+    /// Seed of the first run, every following run uses the next seed.
+    pub seed: u64,
+    /// Simulated time multiplier. Values above 1 trade accuracy for speed.
+    pub speed: f32,
+    /// Runs that survive this long (in seconds) are cut short.
+    pub max_duration: f32,
+    pub format: ReportFormat,
+    pub output: PathBuf,
+}
+
+impl Default for BalanceConfig {
+    fn default() -> Self {
+        Self {
+            runs: 10,
+            seed: 0,
+            speed: 1.,
+            max_duration: 900.,
+            format: ReportFormat::Csv,
+            output: PathBuf::from("balance_report.csv"),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+struct Reports(Vec<RunReport>);
+
+impl Plugin for BalancePlugin {
+    fn build(&self, app: &mut App) {
+        // the frames aren't tied to the wall clock, so the runs play as fast as the CPU allows
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            FRAME_DURATION_S * self.0.speed,
+        )))
+        .insert_resource(self.0.clone())
+        .init_resource::<Reports>()
+        .add_plugins(bot::plugin)
+        .add_systems(OnEnter(Screen::Title), start_first_run)
+        .add_systems(OnEnter(Screen::GameOver), finish_run)
+        .add_systems(Update, cut_long_run.run_if(in_state(Screen::Game)));
+    }
+}
+
+fn start_first_run(
+    config: Res<BalanceConfig>,
+    mut seed: ResMut<RunSeed>,
+    mut next_screen: ResMut<NextTransitionedState>,
+) {
+    seed.next = Some(config.seed);
+    next_screen.set(Screen::Game);
+}
+
+fn cut_long_run(
+    config: Res<BalanceConfig>,
+    stats: Res<RunStats>,
+    mut next_screen: ResMut<NextTransitionedState>,
+) {
+    if stats.duration >= config.max_duration {
+        next_screen.set(Screen::GameOver);
+    }
+}
+
+fn finish_run(
+    config: Res<BalanceConfig>,
+    mut reports: ResMut<Reports>,
+    mut seed: ResMut<RunSeed>,
+    mut next_screen: ResMut<NextTransitionedState>,
+    mut app_exit: EventWriter<AppExit>,
+    stats: Res<RunStats>,
+    score: Res<Score>,
+    rng: Res<GameRng>,
+) {
+    let finished = RunReport::new(
+        reports.0.len(),
+        rng.seed(),
+        &stats,
+        &score,
+        stats.duration >= config.max_duration,
+    );
+    info!(
+        run = finished.run,
+        seed = finished.seed,
+        survival_time = finished.survival_time,
+        score = finished.score,
+        "run finished"
+    );
+    reports.0.push(finished);
+
+    if reports.0.len() < config.runs {
+        seed.next = Some(config.seed.wrapping_add(reports.0.len() as u64));
+        next_screen.set(Screen::Game);
+        return;
+    }
+
+    match report::write(&reports.0, config.format, &config.output) {
+        Ok(()) => {
+            info!(path = ?config.output, "balance report written");
+            app_exit.send(AppExit::Success);
+        }
+        Err(err) => {
+            error!(?err, path = ?config.output, "failed to write the balance report");
+            app_exit.send(AppExit::error());
+        }
+    }
+}
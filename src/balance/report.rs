@@ -0,0 +1,111 @@
+use std::{collections::BTreeMap, fmt::Write as _, fs, io, path::Path, str::FromStr};
+
+use serde::Serialize;
+
+use crate::game::{
+    score::Score,
+    spawn::enemy::EnemyKind,
+    stats::{CoreDamage, RunStats},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown report format '{s}', expected csv or json")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub run: usize,
+    pub seed: u64,
+    pub survival_time: f32,
+    pub score: usize,
+    pub kills: BTreeMap<&'static str, usize>,
+    pub core_damage: Vec<CoreDamage>,
+    pub ammo_used: usize,
+    pub ammo_gained: usize,
+    pub avg_ball_speed_factor: f32,
+    /// The run has been cut short by [`super::BalanceConfig::max_duration`].
+    pub timed_out: bool,
+}
+
+impl RunReport {
+    pub fn new(run: usize, seed: u64, stats: &RunStats, score: &Score, timed_out: bool) -> Self {
+        Self {
+            run,
+            seed,
+            survival_time: stats.duration,
+            score: score.0,
+            kills: EnemyKind::ALL
+                .iter()
+                .map(|kind| (kind.name(), stats.kills_of(*kind)))
+                .collect(),
+            core_damage: stats.core_damage.clone(),
+            ammo_used: stats.ammo_used,
+            ammo_gained: stats.ammo_gained,
+            avg_ball_speed_factor: stats.avg_ball_speed_factor(),
+            timed_out,
+        }
+    }
+}
+
+pub(super) fn write(reports: &[RunReport], format: ReportFormat, path: &Path) -> io::Result<()> {
+    let content = match format {
+        ReportFormat::Csv => to_csv(reports),
+        ReportFormat::Json => serde_json::to_string_pretty(reports)?,
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, content)
+}
+
+fn to_csv(reports: &[RunReport]) -> String {
+    let mut csv = String::from("run,seed,survival_time,score");
+    for kind in EnemyKind::ALL {
+        let _ = write!(csv, ",kills_{}", kind.name());
+    }
+    csv.push_str(",core_damage,ammo_used,ammo_gained,avg_ball_speed_factor,timed_out\n");
+
+    for report in reports {
+        let _ = write!(
+            csv,
+            "{},{},{:.2},{}",
+            report.run, report.seed, report.survival_time, report.score
+        );
+        for kind in EnemyKind::ALL {
+            let _ = write!(csv, ",{}", report.kills[kind.name()]);
+        }
+        // `time:remaining_health` pairs
+        let core_damage = report
+            .core_damage
+            .iter()
+            .map(|dmg| format!("{:.2}:{}", dmg.time, dmg.health))
+            .collect::<Vec<_>>()
+            .join(";");
+        let _ = writeln!(
+            csv,
+            ",{},{},{},{:.3},{}",
+            core_damage,
+            report.ammo_used,
+            report.ammo_gained,
+            report.avg_ball_speed_factor,
+            report.timed_out
+        );
+    }
+
+    csv
+}
@@ -0,0 +1,87 @@
+//! Plays a batch of seeded runs with a bot and writes their statistics to a file.
+//!
+//! `cargo run --release --no-default-features --bin balance -- --runs 50 --seed 7 --out runs.csv`
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use porcle::{
+    balance::{BalanceConfig, BalancePlugin, ReportFormat},
+    AppPlugin,
+};
+
+const USAGE: &str = "\
+Usage: balance [OPTIONS]
+
+Options:
+  --runs <N>            number of runs to play [default: 10]
+  --seed <SEED>         seed of the first run, the following runs increment it [default: 0]
+  --speed <FACTOR>      simulated time multiplier, higher is faster but less accurate [default: 1]
+  --max-duration <S>    cut runs surviving longer than this many seconds [default: 900]
+  --format <csv|json>   report format [default: csv]
+  --out <PATH>          report path [default: balance_report.<format>]";
+
+fn main() -> AppExit {
+    let config = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(config)) => config,
+        Ok(None) => {
+            println!("{USAGE}");
+            return AppExit::Success;
+        }
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            return AppExit::error();
+        }
+    };
+
+    App::new()
+        .add_plugins((
+            AppPlugin {
+                headless: true,
+                ..default()
+            },
+            BalancePlugin(config),
+        ))
+        .run()
+}
+
+/// `None` when the usage was asked for.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<BalanceConfig>, String> {
+    let mut config = BalanceConfig::default();
+    let mut output = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for {arg}"))
+        };
+        match arg.as_str() {
+            "--runs" => config.runs = parse(&value()?)?,
+            "--seed" => config.seed = parse(&value()?)?,
+            "--speed" => config.speed = parse(&value()?)?,
+            "--max-duration" => config.max_duration = parse(&value()?)?,
+            "--format" => config.format = value()?.parse()?,
+            "--out" => output = Some(PathBuf::from(value()?)),
+            "-h" | "--help" => return Ok(None),
+            _ => return Err(format!("unknown argument {arg}")),
+        }
+    }
+
+    if config.speed <= 0. {
+        return Err("--speed has to be positive".into());
+    }
+
+    config.output = output.unwrap_or_else(|| {
+        PathBuf::from(match config.format {
+            ReportFormat::Csv => "balance_report.csv",
+            ReportFormat::Json => "balance_report.json",
+        })
+    });
+    Ok(Some(config))
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{value}'"))
+}
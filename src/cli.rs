@@ -0,0 +1,115 @@
+//! Command-line options of the native binary.
+
+use std::{path::PathBuf, str::FromStr};
+
+use bevy::{prelude::*, window::WindowMode};
+
+use crate::{game::mode::GameMode, screen::Screen};
+
+/// Options the game has been launched with.
+/// Plugins read these from the resource inserted by [`crate::AppPlugin`].
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LaunchOptions {
+    /// Seed of every run.
+    pub(crate) seed: Option<u64>,
+    /// Screen to open once the assets are loaded, skipping the splash screen.
+    pub(crate) screen: Option<Screen>,
+    pub(crate) mode: Option<GameMode>,
+    pub(crate) window_mode: Option<WindowMode>,
+    pub(crate) resolution: Option<Vec2>,
+    pub(crate) mute: bool,
+    /// Replay file to play back instead of taking player input.
+    pub(crate) replay: Option<PathBuf>,
+    /// Base URL of the online leaderboard service.
+    pub(crate) leaderboard_url: Option<String>,
+}
+
+impl LaunchOptions {
+    pub const USAGE: &'static str = "\
+Usage: porcle [OPTIONS]
+
+Options:
+  --seed <SEED>                    seed of every run
+  --screen <game|title|tutorial>   skip the splash screen & open this screen right away
+  --mode <MODE>                    game mode [possible values: endless]
+  --windowed                       start in a window
+  --fullscreen                     start in fullscreen
+  --resolution <WIDTHxHEIGHT>      window size [default: 1024x1024]
+  --mute                           start with the audio muted
+  --replay <PATH>                  play back a recorded run
+  --leaderboard-url <URL>          submit runs to & fetch scores from this online leaderboard";
+
+    /// Parses the arguments of the current process.
+    pub fn from_args() -> Result<Self, ArgsError> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, ArgsError> {
+        let mut options = Self::default();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {arg}"))
+            };
+            match arg.as_str() {
+                "--seed" => options.seed = Some(parse(&value()?)?),
+                "--screen" => options.screen = Some(parse_screen(&value()?)?),
+                "--mode" => options.mode = Some(value()?.parse()?),
+                "--windowed" => options.window_mode = Some(WindowMode::Windowed),
+                "--fullscreen" => options.window_mode = Some(WindowMode::BorderlessFullscreen),
+                "--resolution" => options.resolution = Some(parse_resolution(&value()?)?),
+                "--mute" => options.mute = true,
+                "--replay" => options.replay = Some(PathBuf::from(value()?)),
+                "--leaderboard-url" => options.leaderboard_url = Some(value()?),
+                "-h" | "--help" => return Err(ArgsError::Help),
+                _ => return Err(format!("unknown argument {arg}").into()),
+            }
+        }
+
+        if options.replay.is_some() {
+            // replays always start in game
+            options.screen.get_or_insert(Screen::Game);
+        }
+
+        Ok(options)
+    }
+}
+
+/// Why no [`LaunchOptions`] came out of the arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgsError {
+    /// The usage was asked for.
+    Help,
+    Invalid(String),
+}
+
+impl From<String> for ArgsError {
+    fn from(err: String) -> Self {
+        Self::Invalid(err)
+    }
+}
+
+fn parse_screen(value: &str) -> Result<Screen, String> {
+    match value {
+        "game" => Ok(Screen::Game),
+        "title" => Ok(Screen::Title),
+        "tutorial" => Ok(Screen::Tutorial),
+        _ => Err(format!(
+            "unknown screen '{value}', expected game, title or tutorial"
+        )),
+    }
+}
+
+fn parse_resolution(value: &str) -> Result<Vec2, String> {
+    let (width, height) = value
+        .split_once('x')
+        .ok_or_else(|| format!("invalid resolution '{value}', expected WIDTHxHEIGHT"))?;
+    Ok(Vec2::new(parse(width)?, parse(height)?))
+}
+
+fn parse<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{value}'"))
+}
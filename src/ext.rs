@@ -1,7 +1,7 @@
 #![allow(dead_code)]
 
 use bevy::prelude::*;
-use rand::{rngs::ThreadRng, Rng};
+use rand::Rng;
 
 pub trait Vec2Ext {
     fn to_quat(self) -> Quat;
@@ -62,7 +62,7 @@ pub trait RandExt {
     fn direction(&mut self) -> Dir2;
 }
 
-impl RandExt for ThreadRng {
+impl<R: Rng> RandExt for R {
     fn rotation(&mut self) -> Rot2 {
         self.rotation_range_degrees(360.0)
     }
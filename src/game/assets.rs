@@ -1,8 +1,16 @@
-use bevy::prelude::*;
+use std::marker::PhantomData;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+};
 use bevy_asset_loader::prelude::*;
 use bevy_enoki::prelude::*;
+use serde::de::DeserializeOwned;
+
+use crate::{screen::Screen, ui::palette::Palette};
 
-use crate::screen::Screen;
+use super::audio::{soundtrack::MusicPresets, synth::SynthPresets};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_loading_state(
@@ -12,7 +20,8 @@ pub(super) fn plugin(app: &mut App) {
             .load_collection::<SfxAssets>()
             .load_collection::<MusicAssets>(),
     );
-    app.add_systems(Startup, setup_particles);
+    app.add_systems(Startup, setup_particles)
+        .add_systems(Update, tint_particles);
 }
 
 pub fn assets_exist(
@@ -24,6 +33,51 @@ pub fn assets_exist(
     sprites.is_some() && sfx.is_some() && music.is_some() && particles.is_some()
 }
 
+/// Loads RON files with the given extensions as `A`, so they hot reload like any asset.
+pub struct RonAssetLoader<A> {
+    extensions: &'static [&'static str],
+    _asset: PhantomData<fn() -> A>,
+}
+
+impl<A> RonAssetLoader<A> {
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            _asset: PhantomData,
+        }
+    }
+}
+
+impl<A: Asset + DeserializeOwned> AssetLoader for RonAssetLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<A, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
+
+/// Whether an asset of the type was hot reloaded, to rebuild what's made from it.
+pub fn asset_modified<A: Asset>(mut asset_r: EventReader<AssetEvent<A>>) -> bool {
+    asset_r
+        .read()
+        .filter(|ev| matches!(ev, AssetEvent::Modified { .. }))
+        .count()
+        > 0
+}
+
 #[derive(AssetCollection, Resource)]
 pub struct SpriteAssets {
     #[asset(path = "images/transition_circle.png")]
@@ -58,12 +112,16 @@ pub struct SfxAssets {
     pub button_hover: Handle<AudioSource>,
     #[asset(path = "audio/sfx/button_press.ogg")]
     pub button_click: Handle<AudioSource>,
+    #[asset(path = "audio/sfx.synth.ron")]
+    pub synth_presets: Handle<SynthPresets>,
 }
 
 #[derive(AssetCollection, Resource)]
 pub struct MusicAssets {
     #[asset(path = "audio/soundtracks/track_1.ogg")]
     pub track_1: Handle<AudioSource>,
+    #[asset(path = "audio/soundtrack.music.ron")]
+    pub presets: Handle<MusicPresets>,
 }
 
 // todo: use asset_loader for particles too
@@ -140,3 +198,50 @@ fn setup_particles(
         ball: ass.load("particles/ball.particle.ron"),
     });
 }
+
+/// Re-colours the particle gradients from the [`Palette`], once they load & on every change.
+fn tint_particles(
+    particles: Option<Res<ParticleAssets>>,
+    palette: Res<Palette>,
+    mut effects: ResMut<Assets<Particle2dEffect>>,
+    mut effect_r: EventReader<AssetEvent<Particle2dEffect>>,
+) {
+    let Some(particles) = particles else {
+        return;
+    };
+    // our own edits send `Modified`, only loads count
+    let loaded = effect_r
+        .read()
+        .any(|ev| matches!(ev, AssetEvent::LoadedWithDependencies { .. }));
+    if !loaded && !palette.is_changed() {
+        return;
+    }
+
+    // the colour of each gradient point, by index
+    let gradients = [
+        (&particles.gun, vec![(1, palette.bullet)]),
+        (
+            &particles.enemy,
+            vec![(0, palette.enemy), (1, palette.enemy_projectile)],
+        ),
+        (&particles.reflection, vec![(0, palette.ball)]),
+        (&particles.core, vec![(0, palette.gears_disabled)]),
+        (&particles.bg, vec![(1, palette.ball_fast)]),
+        (&particles.ball, vec![(0, palette.ball_fast)]),
+    ];
+    for (handle, colors) in gradients {
+        let Some(curve) = effects
+            .get_mut(handle)
+            .and_then(|effect| effect.color_curve.as_mut())
+        else {
+            continue;
+        };
+        for (i, color) in colors {
+            if let Some((point, ..)) = curve.points.get_mut(i) {
+                // the effect files hold the sRGB values as they are, keep it that way
+                let color = color.to_srgba();
+                *point = LinearRgba::new(color.red, color.green, color.blue, point.alpha);
+            }
+        }
+    }
+}
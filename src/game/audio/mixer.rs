@@ -0,0 +1,171 @@
+//! Volume buses every sound plays through.
+//!
+//! A sound's [`PlaybackSettings`] volume is its level within its [`AudioBus`],
+//! the bus gain is applied to the sink every frame so setting changes & ducking
+//! also reach the sounds already playing.
+
+use bevy::{audio::AudioPlaySet, prelude::*, utils::HashMap};
+
+use crate::{cli::LaunchOptions, game::core::TakenDamage, settings::Settings};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<AudioBus>()
+        .init_resource::<Ducking>()
+        .observe(duck_bus)
+        .add_systems(Update, (duck_on_damage, update_ducking).chain())
+        .add_systems(PostUpdate, apply_bus_volumes.after(AudioPlaySet));
+}
+
+/// Master gain at the default master volume setting.
+const MASTER_VOLUME: f32 = 0.3;
+/// Seconds for a bus to reach its ducked gain.
+const DUCK_ATTACK: f32 = 0.05;
+/// Seconds for a bus to recover from ducking.
+const DUCK_RELEASE: f32 = 0.4;
+/// Music gain during a hit-stop.
+const HIT_STOP_MUSIC_GAIN: f32 = 0.5;
+
+/// The bus a sound plays through, everything also goes through [`AudioBus::Master`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub enum AudioBus {
+    Master,
+    Music,
+    Sfx,
+    Ui,
+}
+
+impl AudioBus {
+    /// The volume setting of the bus alone, 0 when muted.
+    fn level(self, settings: &Settings) -> f32 {
+        let (volume, muted) = match self {
+            AudioBus::Master => (
+                MASTER_VOLUME * settings.master_volume,
+                settings.muted.master,
+            ),
+            AudioBus::Music => (settings.music_volume, settings.muted.music),
+            AudioBus::Sfx => (settings.sfx_volume, settings.muted.sfx),
+            AudioBus::Ui => (settings.ui_volume, settings.muted.ui),
+        };
+        if muted {
+            0.
+        } else {
+            volume
+        }
+    }
+}
+
+/// Lowers the gain of a bus for a while, the deepest active duck wins.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DuckBus {
+    pub bus: AudioBus,
+    /// Gain multiplier while ducked.
+    pub gain: f32,
+    /// Seconds before the bus starts recovering.
+    pub duration: f32,
+}
+
+impl DuckBus {
+    /// Ducks the music for the freeze of a hit.
+    pub fn hit_stop(duration: f32) -> Self {
+        Self {
+            bus: AudioBus::Music,
+            gain: HIT_STOP_MUSIC_GAIN,
+            duration,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+struct Ducking {
+    /// Target gain of each ducked bus & the real time it lasts until.
+    held: HashMap<AudioBus, (f32, f32)>,
+    /// Current gain of each bus that isn't fully recovered.
+    gains: HashMap<AudioBus, f32>,
+}
+
+impl Ducking {
+    fn gain(&self, bus: AudioBus) -> f32 {
+        self.gains.get(&bus).copied().unwrap_or(1.)
+    }
+}
+
+/// The effective gain of a bus, including the master bus.
+fn bus_gain(bus: AudioBus, settings: &Settings, options: &LaunchOptions, ducking: &Ducking) -> f32 {
+    if options.mute {
+        return 0.;
+    }
+    let master = AudioBus::Master.level(settings) * ducking.gain(AudioBus::Master);
+    match bus {
+        AudioBus::Master => master,
+        _ => master * bus.level(settings) * ducking.gain(bus),
+    }
+}
+
+fn duck_bus(trigger: Trigger<DuckBus>, mut ducking: ResMut<Ducking>, time: Res<Time<Real>>) {
+    let ev = trigger.event();
+    let until = time.elapsed_seconds() + ev.duration;
+    let held = ducking.held.entry(ev.bus).or_insert((ev.gain, until));
+    *held = (held.0.min(ev.gain), held.1.max(until));
+}
+
+fn duck_on_damage(mut cmd: Commands, mut damage_r: EventReader<TakenDamage>) {
+    if damage_r.read().count() > 0 {
+        cmd.trigger(DuckBus {
+            bus: AudioBus::Music,
+            gain: 0.4,
+            duration: 0.6,
+        });
+    }
+}
+
+fn update_ducking(mut ducking: ResMut<Ducking>, real_time: Res<Time<Real>>) {
+    let now = real_time.elapsed_seconds();
+    let dt = real_time.delta_seconds();
+    ducking.held.retain(|_, (_, until)| *until > now);
+
+    let Ducking { held, gains } = &mut *ducking;
+    for bus in [
+        AudioBus::Master,
+        AudioBus::Music,
+        AudioBus::Sfx,
+        AudioBus::Ui,
+    ] {
+        let target = held.get(&bus).map_or(1., |(gain, _)| *gain);
+
+        let gain = gains.get(&bus).copied().unwrap_or(1.);
+        let duration = if target < gain {
+            DUCK_ATTACK
+        } else {
+            DUCK_RELEASE
+        };
+        let step = dt / duration;
+        let gain = gain + (target - gain).clamp(-step, step);
+        if gain >= 1. {
+            gains.remove(&bus);
+        } else {
+            gains.insert(bus, gain);
+        }
+    }
+}
+
+/// Runs after the new sinks get created, so no sound plays a frame at the wrong volume.
+fn apply_bus_volumes(
+    source_q: Query<(
+        &AudioBus,
+        &PlaybackSettings,
+        AnyOf<(&AudioSink, &SpatialAudioSink)>,
+    )>,
+    settings: Res<Settings>,
+    options: Res<LaunchOptions>,
+    ducking: Res<Ducking>,
+) {
+    for (bus, playback, sinks) in &source_q {
+        let volume = playback.volume.get() * bus_gain(*bus, &settings, &options, &ducking);
+        match sinks {
+            (Some(sink), _) => sink.set_volume(volume),
+            (_, Some(sink)) => sink.set_volume(volume),
+            _ => {}
+        }
+    }
+}
@@ -1,8 +1,10 @@
+pub mod mixer;
 pub mod sfx;
 pub mod soundtrack;
+pub mod synth;
 
 use bevy::prelude::*;
 
 pub fn plugin(app: &mut App) {
-    app.add_plugins((sfx::plugin, soundtrack::plugin));
+    app.add_plugins((mixer::plugin, sfx::plugin, soundtrack::plugin));
 }
@@ -1,22 +1,288 @@
 use bevy::{
-    audio::{PlaybackMode, Volume},
+    audio::{PlaybackMode, SpatialScale, Volume},
     prelude::*,
+    utils::HashMap,
 };
+use rand::{seq::SliceRandom, thread_rng, Rng};
+use serde::Deserialize;
+
+use crate::{
+    game::{
+        assets::{asset_modified, RonAssetLoader, SfxAssets},
+        core::TakenDamage,
+        spawn::{
+            enemy::{EnemyHit, EnemyKilled},
+            level::Core,
+        },
+    },
+    settings::Settings,
+    GAME_SIZE,
+};
+
+use super::{mixer::AudioBus, synth::SynthPresets};
 
 pub(super) fn plugin(app: &mut App) {
-    app.observe(play_sfx);
+    app.init_asset::<SynthPresets>()
+        .register_asset_loader(RonAssetLoader::<SynthPresets>::new(&["synth.ron"]))
+        .observe(play_sfx)
+        .add_systems(
+            Update,
+            (
+                build_sfx_bank.run_if(
+                    resource_exists::<SfxAssets>.and_then(
+                        resource_added::<SfxAssets>.or_else(asset_modified::<SynthPresets>),
+                    ),
+                ),
+                play_event_sfx,
+            ),
+        );
 }
 
-fn play_sfx(trigger: Trigger<PlaySfx>, mut commands: Commands) {
-    commands.spawn(AudioSourceBundle {
-        source: trigger.event().0.clone(),
-        settings: PlaybackSettings {
-            mode: PlaybackMode::Despawn,
-            volume: Volume::new(0.175),
-            ..default()
-        },
+/// Level of the sounds within their bus.
+const SFX_VOLUME: f32 = 0.175;
+/// Volume lost by the sounds farthest from the core, with the distance attenuation on.
+const DISTANCE_ATTENUATION: f32 = 0.6;
+
+/// Gameplay & UI sounds, see [`SfxBank`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Deserialize)]
+pub enum SfxKey {
+    ButtonHover,
+    ButtonClick,
+    /// Pitched by the ball speed.
+    BallReflect,
+    WallBounce,
+    Capture,
+    Release,
+    GunShot,
+    GunEmpty,
+    EnemyHit,
+    EnemyDeath,
+    CoreDamage,
+    GearLoss,
+    /// A CCW paddle revolution refilling the ammo.
+    Reload,
+    /// A CW paddle revolution spawning a new ball.
+    Recall,
+}
+
+impl SfxKey {
+    fn bus(self) -> AudioBus {
+        match self {
+            SfxKey::ButtonHover | SfxKey::ButtonClick => AudioBus::Ui,
+            _ => AudioBus::Sfx,
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlaySfx {
+    pub key: SfxKey,
+    /// Multiplies the pitch of the sound.
+    pub pitch: f32,
+    /// World position of the emitter, panned relative to the core.
+    pub position: Option<Vec2>,
+}
+
+impl PlaySfx {
+    pub fn new(key: SfxKey) -> Self {
+        Self {
+            key,
+            pitch: 1.,
+            position: None,
+        }
+    }
+
+    pub fn with_pitch(mut self, pitch: f32) -> Self {
+        self.pitch = pitch;
+        self
+    }
+
+    pub fn at(mut self, position: Vec2) -> Self {
+        self.position = Some(position);
+        self
+    }
+}
+
+#[derive(Debug)]
+struct SfxSound {
+    /// One gets picked at random.
+    variants: Vec<Handle<AudioSource>>,
+    volume: f32,
+    /// Max random offset of the volume multiplier.
+    volume_jitter: f32,
+    /// Max random offset of the pitch multiplier.
+    pitch_jitter: f32,
+    /// Min seconds between two plays, so a busy frame doesn't stack the same sound.
+    cooldown: f32,
+}
+
+impl SfxSound {
+    fn new(variants: Vec<Handle<AudioSource>>) -> Self {
+        Self {
+            variants,
+            volume: 1.,
+            volume_jitter: 0.1,
+            pitch_jitter: 0.08,
+            cooldown: 0.05,
+        }
+    }
+
+    fn volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    fn cooldown(mut self, cooldown: f32) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+}
+
+/// Every [`SfxKey`] with its variants & playback rules.
+#[derive(Resource, Debug)]
+pub struct SfxBank {
+    sounds: HashMap<SfxKey, SfxSound>,
+    /// Real time of the last play of each sound, for the cooldowns.
+    last_played: HashMap<SfxKey, f32>,
+}
+
+/// Until there are recordings of them, the gameplay sounds are synthesized.
+/// Rebuilt when the presets change.
+fn build_sfx_bank(
+    mut cmd: Commands,
+    sfx: Res<SfxAssets>,
+    presets: Res<Assets<SynthPresets>>,
+    mut sources: ResMut<Assets<AudioSource>>,
+) {
+    use SfxKey as K;
+
+    let presets = presets.get(&sfx.synth_presets);
+    let mut synth = |key: SfxKey| {
+        let variants = presets
+            .and_then(|presets| presets.0.get(&key))
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, params)| sources.add(params.synthesize(i as u64)))
+            .collect();
+        SfxSound::new(variants)
+    };
+    let sounds = HashMap::from_iter([
+        (
+            K::ButtonHover,
+            SfxSound::new(vec![sfx.button_hover.clone()]).cooldown(0.03),
+        ),
+        (
+            K::ButtonClick,
+            SfxSound::new(vec![sfx.button_click.clone()]).cooldown(0.03),
+        ),
+        (K::BallReflect, synth(K::BallReflect)),
+        (K::WallBounce, synth(K::WallBounce).volume(0.8)),
+        (K::Capture, synth(K::Capture)),
+        (K::Release, synth(K::Release)),
+        (K::GunShot, synth(K::GunShot).volume(0.6).cooldown(0.03)),
+        (K::GunEmpty, synth(K::GunEmpty).cooldown(0.2)),
+        (K::EnemyHit, synth(K::EnemyHit)),
+        (K::EnemyDeath, synth(K::EnemyDeath)),
+        (
+            K::CoreDamage,
+            synth(K::CoreDamage).volume(1.3).cooldown(0.2),
+        ),
+        (K::GearLoss, synth(K::GearLoss).cooldown(0.2)),
+        (K::Reload, synth(K::Reload).cooldown(0.2)),
+        (K::Recall, synth(K::Recall).cooldown(0.2)),
+    ]);
+
+    cmd.insert_resource(SfxBank {
+        sounds,
+        last_played: default(),
     });
 }
 
-#[derive(Event)]
-pub struct PlaySfx(pub Handle<AudioSource>);
+fn play_sfx(
+    trigger: Trigger<PlaySfx>,
+    mut commands: Commands,
+    bank: Option<ResMut<SfxBank>>,
+    core_q: Query<&GlobalTransform, With<Core>>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+) {
+    let Some(mut bank) = bank else {
+        return;
+    };
+    let ev = trigger.event();
+    let now = time.elapsed_seconds();
+    let Some(sound) = bank.sounds.get(&ev.key) else {
+        return;
+    };
+    if bank
+        .last_played
+        .get(&ev.key)
+        .is_some_and(|at| now - at < sound.cooldown)
+    {
+        return;
+    }
+
+    // cosmetic, so this doesn't use the `GameRng` replays depend on
+    let mut rng = thread_rng();
+    let Some(source) = sound.variants.choose(&mut rng).cloned() else {
+        return;
+    };
+    let mut volume = sound.volume * (1. + rng.gen_range(-1f32..=1.) * sound.volume_jitter);
+    let pitch = ev.pitch * (1. + rng.gen_range(-1f32..=1.) * sound.pitch_jitter);
+
+    // only the x offset matters, the listener's ears are on the core at the arena edges
+    let core = core_q.get_single().map(|t| t.translation().truncate());
+    let emitter = match (ev.position, core) {
+        (Some(position), Ok(core)) => {
+            let offset = position - core;
+            if settings.distance_attenuation {
+                let distance = offset.length() / (GAME_SIZE / 2. * std::f32::consts::SQRT_2);
+                volume *= 1. - DISTANCE_ATTENUATION * distance.min(1.);
+            }
+            let x = offset.x.clamp(-GAME_SIZE / 2., GAME_SIZE / 2.);
+            Some(Vec2::new(core.x + x, core.y))
+        }
+        _ => None,
+    };
+
+    let mut entity = commands.spawn((
+        AudioSourceBundle {
+            source,
+            settings: PlaybackSettings {
+                mode: PlaybackMode::Despawn,
+                volume: Volume::new(SFX_VOLUME * volume),
+                speed: pitch,
+                spatial: emitter.is_some(),
+                // the arena width is 1 unit, so the panning doesn't also attenuate
+                spatial_scale: Some(SpatialScale::new_2d(1. / GAME_SIZE)),
+                ..default()
+            },
+        },
+        ev.key.bus(),
+    ));
+    if let Some(emitter) = emitter {
+        entity.insert(TransformBundle::from_transform(
+            Transform::from_translation(emitter.extend(0.)),
+        ));
+    }
+    bank.last_played.insert(ev.key, now);
+}
+
+fn play_event_sfx(
+    mut cmd: Commands,
+    mut hit_r: EventReader<EnemyHit>,
+    mut kill_r: EventReader<EnemyKilled>,
+    mut damage_r: EventReader<TakenDamage>,
+) {
+    // the cooldowns would skip the others anyway
+    if let Some(hit) = hit_r.read().last() {
+        cmd.trigger(PlaySfx::new(SfxKey::EnemyHit).at(hit.position));
+    }
+    if let Some(kill) = kill_r.read().last() {
+        cmd.trigger(PlaySfx::new(SfxKey::EnemyDeath).at(kill.position));
+    }
+    if let Some(damage) = damage_r.read().last() {
+        cmd.trigger(PlaySfx::new(SfxKey::CoreDamage).at(damage.position));
+    }
+}
@@ -1,46 +1,406 @@
 use bevy::{
     audio::{PlaybackMode, Volume},
     prelude::*,
+    utils::HashMap,
+};
+use serde::Deserialize;
+
+use crate::{
+    game::{
+        assets::{asset_modified, MusicAssets, RonAssetLoader},
+        ball::MaxBallSpeedFactor,
+        core::TakenDamage,
+        spawn::{
+            enemy::Enemy,
+            level::{Core, Health, GEAR_COUNT},
+        },
+    },
+    screen::Screen,
+    AppSet,
+};
+
+use super::{
+    mixer::AudioBus,
+    synth::{SynthLoop, SynthParams},
 };
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<Music>();
-    app.observe(play_soundtrack);
+    app.register_type::<Stem>()
+        .init_asset::<MusicPresets>()
+        .register_asset_loader(RonAssetLoader::<MusicPresets>::new(&["music.ron"]))
+        .init_resource::<MusicIntensity>()
+        .init_resource::<MusicPass>()
+        .observe(play_soundtrack)
+        .add_systems(OnEnter(Screen::Loaded), build_music_bank)
+        .add_systems(Update, loop_soundtrack.in_set(AppSet::TickTimers))
+        .add_systems(
+            Update,
+            (
+                build_music_bank.run_if(
+                    resource_exists::<MusicAssets>.and_then(asset_modified::<MusicPresets>),
+                ),
+                update_music_intensity,
+                mix_stems,
+                pause_stems_with_game,
+                play_stinger,
+            )
+                .chain(),
+        );
+}
+
+/// Level of the music within its bus.
+const MUSIC_VOLUME: f32 = 0.275;
+/// Seconds for a track or layer to fade fully in or out.
+const CROSSFADE_DURATION: f32 = 1.5;
+/// Enemies on screen for the full threat layer.
+const THREAT_ENEMY_COUNT: f32 = 12.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Deserialize)]
+pub enum MusicTrack {
+    Title,
+    Gameplay,
+    GameOver,
+}
+
+/// Synchronised parts of a track, faded by the [`MusicIntensity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Deserialize)]
+enum MusicLayer {
+    /// Always on.
+    Base,
+    /// Follows the ball speed.
+    Speed,
+    /// Follows the number of enemies.
+    Threat,
+    /// Follows the core damage.
+    Danger,
+}
+
+/// Beat grid of a track.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Tempo {
+    pub bpm: f32,
+    /// Seconds before the first beat.
+    pub offset: f32,
+    pub beats_per_bar: u32,
+    /// Bars after which every stem restarts together, cutting the tail of a recording
+    /// that isn't a whole number of bars. Without it the stems loop on their own.
+    #[serde(default)]
+    pub loop_bars: Option<u32>,
+}
+
+impl Default for Tempo {
+    fn default() -> Self {
+        Self {
+            bpm: 120.,
+            offset: 0.,
+            beats_per_bar: 4,
+            loop_bars: None,
+        }
+    }
+}
+
+impl Tempo {
+    pub fn beat_duration(&self) -> f32 {
+        60. / self.bpm
+    }
+
+    pub fn loop_duration(&self) -> Option<f32> {
+        self.loop_bars
+            .map(|bars| (bars * self.beats_per_bar) as f32 * self.beat_duration())
+    }
+}
+
+impl MusicTrack {
+    const ALL: [MusicTrack; 3] = [
+        MusicTrack::Title,
+        MusicTrack::Gameplay,
+        MusicTrack::GameOver,
+    ];
+
+    /// Recorded base layer, the other layers are synthesized until they're recorded.
+    fn recording(self, music: &MusicAssets) -> Option<Handle<AudioSource>> {
+        match self {
+            MusicTrack::Title | MusicTrack::Gameplay => Some(music.track_1.clone()),
+            MusicTrack::GameOver => None,
+        }
+    }
+}
+
+/// Tempo & synthesized placeholders of every track.
+#[derive(Asset, TypePath, Debug, Deserialize)]
+#[serde(transparent)]
+pub struct MusicPresets(HashMap<MusicTrack, TrackPresets>);
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TrackPresets {
+    tempo: Tempo,
+    layers: HashMap<MusicLayer, SynthLoop>,
+    /// One-shot played over the track when the core takes damage.
+    stinger: Option<SynthParams>,
+}
+
+/// The tempo, stems & stingers of every track, layers without a stem get skipped.
+#[derive(Resource, Debug, Default)]
+pub struct MusicBank {
+    tempos: HashMap<MusicTrack, Tempo>,
+    stems: HashMap<MusicTrack, Vec<(MusicLayer, Handle<AudioSource>)>>,
+    stingers: HashMap<MusicTrack, Handle<AudioSource>>,
+}
+
+impl MusicBank {
+    pub fn tempo(&self, track: MusicTrack) -> Tempo {
+        self.tempos.get(&track).copied().unwrap_or_default()
+    }
+}
+
+#[derive(Event)]
+pub enum PlayMusic {
+    /// Crossfades to the track, unless it's already playing.
+    Track(MusicTrack),
+    /// Crossfades to the track from its start, e.g. to line it up with the beat clock.
+    Restart(MusicTrack),
+    /// Fades the music out.
+    #[allow(dead_code)]
+    Disable,
+}
+
+/// How far the playing track is into its loop, on the game time the stems pause with.
+#[derive(Resource, Debug, Default)]
+pub struct MusicPass {
+    pub track: Option<MusicTrack>,
+    /// Loops completed since the track started.
+    pub count: u32,
+    /// Seconds into the current loop.
+    pub elapsed: f32,
+}
+
+/// 0-1 levels driving the [`MusicLayer`]s.
+#[derive(Resource, Debug, Default)]
+pub struct MusicIntensity {
+    pub speed: f32,
+    pub threat: f32,
+    pub danger: f32,
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+struct Stem {
+    track: MusicTrack,
+    layer: MusicLayer,
+    /// Current fade level.
+    gain: f32,
+    fading_out: bool,
 }
 
 fn play_soundtrack(
     trigger: Trigger<PlayMusic>,
     mut commands: Commands,
-    soundtrack_query: Query<Entity, With<Music>>,
+    mut stem_q: Query<&mut Stem>,
+    mut pass: ResMut<MusicPass>,
+    bank: Option<Res<MusicBank>>,
 ) {
-    for entity in &soundtrack_query {
-        commands.entity(entity).despawn_recursive();
+    let (track, restart) = match trigger.event() {
+        PlayMusic::Track(track) => (Some(*track), false),
+        PlayMusic::Restart(track) => (Some(*track), true),
+        PlayMusic::Disable => (None, false),
+    };
+    if track.is_some()
+        && !restart
+        && stem_q
+            .iter()
+            .any(|s| Some(s.track) == track && !s.fading_out)
+    {
+        return;
     }
 
-    let handle = match trigger.event() {
-        PlayMusic::Track(key) => key.clone(),
-        PlayMusic::Disable => return,
+    for mut stem in &mut stem_q {
+        stem.fading_out = true;
+    }
+    *pass = MusicPass { track, ..default() };
+
+    let (Some(track), Some(bank)) = (track, bank) else {
+        return;
+    };
+    spawn_stems(&mut commands, &bank, track, |_| 0.);
+}
+
+/// Spawned in the same frame to stay in sync.
+fn spawn_stems(
+    cmd: &mut Commands,
+    bank: &MusicBank,
+    track: MusicTrack,
+    gain: impl Fn(MusicLayer) -> f32,
+) {
+    for (layer, handle) in bank.stems.get(&track).into_iter().flatten() {
+        let gain = gain(*layer);
+        cmd.spawn((
+            Name::new("Music stem"),
+            AudioSourceBundle {
+                source: handle.clone(),
+                settings: PlaybackSettings {
+                    mode: PlaybackMode::Loop,
+                    volume: Volume::new(MUSIC_VOLUME * gain),
+                    ..default()
+                },
+            },
+            Stem {
+                track,
+                layer: *layer,
+                gain,
+                fading_out: false,
+            },
+            AudioBus::Music,
+        ));
+    }
+}
+
+/// Restarts the stems of the playing track every loop of its tempo, at their current gains,
+/// so the synthesized layers stay on the bars of the recording.
+pub fn loop_soundtrack(
+    mut cmd: Commands,
+    mut pass: ResMut<MusicPass>,
+    stem_q: Query<(Entity, &Stem)>,
+    bank: Option<Res<MusicBank>>,
+    time: Res<Time<Virtual>>,
+) {
+    let (Some(track), Some(bank)) = (pass.track, bank) else {
+        return;
+    };
+    pass.elapsed += time.delta_seconds();
+    let Some(loop_duration) = bank.tempo(track).loop_duration() else {
+        return;
+    };
+    if pass.elapsed < loop_duration {
+        return;
+    }
+    pass.elapsed -= loop_duration;
+    pass.count += 1;
+
+    let mut gains = HashMap::new();
+    for (e, stem) in &stem_q {
+        if stem.track == track && !stem.fading_out {
+            gains.insert(stem.layer, stem.gain);
+            cmd.entity(e).despawn_recursive();
+        }
+    }
+    spawn_stems(&mut cmd, &bank, track, |layer| {
+        gains.get(&layer).copied().unwrap_or(0.)
+    });
+}
+
+/// Rebuilt when the presets change, the playing stems keep their old sources.
+pub fn build_music_bank(
+    mut cmd: Commands,
+    music: Res<MusicAssets>,
+    presets: Res<Assets<MusicPresets>>,
+    mut sources: ResMut<Assets<AudioSource>>,
+) {
+    let presets = presets.get(&music.presets);
+    let mut bank = MusicBank::default();
+    for track in MusicTrack::ALL {
+        let mut stems: Vec<_> = track
+            .recording(&music)
+            .map(|handle| (MusicLayer::Base, handle))
+            .into_iter()
+            .collect();
+        let Some(presets) = presets.and_then(|presets| presets.0.get(&track)) else {
+            bank.stems.insert(track, stems);
+            continue;
+        };
+        for (layer, synth_loop) in &presets.layers {
+            stems.push((
+                *layer,
+                sources.add(synth_loop.synthesize(presets.tempo.bpm, *layer as u64)),
+            ));
+        }
+        bank.tempos.insert(track, presets.tempo);
+        bank.stems.insert(track, stems);
+        if let Some(stinger) = &presets.stinger {
+            bank.stingers
+                .insert(track, sources.add(stinger.synthesize(0)));
+        }
+    }
+    cmd.insert_resource(bank);
+}
+
+fn update_music_intensity(
+    mut intensity: ResMut<MusicIntensity>,
+    ball_speed_factor: Res<MaxBallSpeedFactor>,
+    enemy_q: Query<(), With<Enemy>>,
+    core_q: Query<&Health, With<Core>>,
+) {
+    intensity.speed = ball_speed_factor.0.clamp(0., 1.);
+    intensity.threat = (enemy_q.iter().count() as f32 / THREAT_ENEMY_COUNT).min(1.);
+    intensity.danger = core_q
+        .get_single()
+        .map_or(0., |hp| 1. - hp.0 as f32 / GEAR_COUNT as f32);
+}
+
+/// Fades the stems toward their layer level.
+fn mix_stems(
+    mut cmd: Commands,
+    mut stem_q: Query<(Entity, &mut Stem, &mut PlaybackSettings)>,
+    intensity: Res<MusicIntensity>,
+    time: Res<Time<Real>>,
+) {
+    let step = time.delta_seconds() / CROSSFADE_DURATION;
+
+    for (e, mut stem, mut playback) in &mut stem_q {
+        let target = match (stem.fading_out, stem.layer) {
+            (true, _) => 0.,
+            (false, MusicLayer::Base) => 1.,
+            (false, MusicLayer::Speed) => intensity.speed,
+            (false, MusicLayer::Threat) => intensity.threat,
+            (false, MusicLayer::Danger) => intensity.danger,
+        };
+        stem.gain += (target - stem.gain).clamp(-step, step);
+
+        if stem.fading_out && stem.gain <= 0. {
+            cmd.entity(e).despawn_recursive();
+        } else {
+            playback.volume = Volume::new(MUSIC_VOLUME * stem.gain);
+        }
+    }
+}
+
+/// Keeps the music on the beat clock, which stops with the game time.
+fn pause_stems_with_game(stem_q: Query<&AudioSink, With<Stem>>, time: Res<Time<Virtual>>) {
+    for sink in &stem_q {
+        if time.is_paused() && !sink.is_paused() {
+            sink.pause();
+        } else if !time.is_paused() && sink.is_paused() {
+            sink.play();
+        }
+    }
+}
+
+fn play_stinger(
+    mut cmd: Commands,
+    mut damage_r: EventReader<TakenDamage>,
+    stem_q: Query<&Stem>,
+    bank: Option<Res<MusicBank>>,
+) {
+    if damage_r.read().count() == 0 {
+        return;
+    }
+    let Some(track) = stem_q.iter().find(|s| !s.fading_out).map(|s| s.track) else {
+        return;
+    };
+    let Some(stinger) = bank.and_then(|bank| bank.stingers.get(&track).cloned()) else {
+        return;
     };
-    commands.spawn((
+
+    cmd.spawn((
+        Name::new("Music stinger"),
         AudioSourceBundle {
-            source: handle,
+            source: stinger,
             settings: PlaybackSettings {
-                mode: PlaybackMode::Loop,
-                volume: Volume::new(0.275),
+                mode: PlaybackMode::Despawn,
+                volume: Volume::new(MUSIC_VOLUME),
                 ..default()
             },
         },
-        Music,
+        AudioBus::Music,
     ));
 }
-
-#[derive(Event)]
-pub enum PlayMusic {
-    #[allow(dead_code)]
-    Track(Handle<AudioSource>),
-    Disable,
-}
-
-#[derive(Component, Reflect)]
-#[reflect(Component)]
-struct Music;
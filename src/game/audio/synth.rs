@@ -0,0 +1,208 @@
+//! A tiny synthesiser rendering short effects into [`AudioSource`]s,
+//! so every sound has something to play before there's a recording of it.
+//!
+//! The presets are RON assets, `assets/audio/sfx.synth.ron` & `assets/audio/soundtrack.music.ron`,
+//! so tweaks hot reload.
+
+use std::f32::consts::TAU;
+
+use bevy::{prelude::*, utils::HashMap};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Deserialize;
+
+use super::sfx::SfxKey;
+
+const SAMPLE_RATE: u32 = 44_100;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub enum Waveform {
+    #[default]
+    Sine,
+    Square,
+    Saw,
+    Triangle,
+    /// Random values held for a cycle, low frequencies rumble & high ones hiss.
+    Noise,
+}
+
+/// Seconds of each stage, the sustain is the level held for `hold` seconds.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct Envelope {
+    pub attack: f32,
+    pub decay: f32,
+    pub sustain: f32,
+    pub hold: f32,
+    pub release: f32,
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Self {
+            attack: 0.005,
+            decay: 0.05,
+            sustain: 0.5,
+            hold: 0.05,
+            release: 0.1,
+        }
+    }
+}
+
+impl Envelope {
+    fn duration(&self) -> f32 {
+        self.attack + self.decay + self.hold + self.release
+    }
+
+    fn level(&self, t: f32) -> f32 {
+        let decay_start = self.attack;
+        let hold_start = decay_start + self.decay;
+        let release_start = hold_start + self.hold;
+        if t < decay_start {
+            t / self.attack
+        } else if t < hold_start {
+            1. + (self.sustain - 1.) * (t - decay_start) / self.decay
+        } else if t < release_start {
+            self.sustain
+        } else {
+            self.sustain * (1. - (t - release_start) / self.release).max(0.)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SynthParams {
+    pub wave: Waveform,
+    /// Hz at the start.
+    pub freq: f32,
+    /// Hz at the end, swept to exponentially. Stays at `freq` when unset.
+    pub freq_end: Option<f32>,
+    /// Part of the square wave cycle spent high.
+    pub duty: f32,
+    pub envelope: Envelope,
+    pub volume: f32,
+}
+
+impl Default for SynthParams {
+    fn default() -> Self {
+        Self {
+            wave: default(),
+            freq: 440.,
+            freq_end: None,
+            duty: 0.5,
+            envelope: default(),
+            volume: 1.,
+        }
+    }
+}
+
+impl SynthParams {
+    /// The seed picks the noise, so the variants of a sound differ.
+    pub fn synthesize(&self, seed: u64) -> AudioSource {
+        to_audio_source(&self.render(seed))
+    }
+
+    fn render(&self, seed: u64) -> Vec<f32> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let duration = self.envelope.duration();
+        let freq = self.freq.max(1.);
+        let freq_end = self.freq_end.unwrap_or(freq).max(1.);
+        let sample_count = (duration * SAMPLE_RATE as f32) as usize;
+
+        let mut phase = 0f32;
+        let mut noise = rng.gen_range(-1f32..=1.);
+        (0..sample_count)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                phase += freq * (freq_end / freq).powf(t / duration) / SAMPLE_RATE as f32;
+                if phase >= 1. {
+                    phase = phase.fract();
+                    noise = rng.gen_range(-1f32..=1.);
+                }
+
+                let sample = match self.wave {
+                    Waveform::Sine => (phase * TAU).sin(),
+                    Waveform::Square => {
+                        if phase < self.duty {
+                            1.
+                        } else {
+                            -1.
+                        }
+                    }
+                    Waveform::Saw => 2. * phase - 1.,
+                    Waveform::Triangle => 1. - 4. * (phase - 0.5).abs(),
+                    Waveform::Noise => noise,
+                };
+                sample * self.envelope.level(t) * self.volume
+            })
+            .collect()
+    }
+}
+
+/// Notes repeating every few beats, a placeholder music stem.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SynthLoop {
+    /// Length of the loop.
+    pub beats: u32,
+    pub notes: Vec<SynthNote>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SynthNote {
+    /// Start within the loop.
+    pub beat: f32,
+    pub sound: SynthParams,
+}
+
+impl SynthLoop {
+    /// Renders a loop lasting exactly its beats at the tempo,
+    /// the notes ringing past its end wrap around to its start.
+    pub fn synthesize(&self, bpm: f32, seed: u64) -> AudioSource {
+        let beat_samples = 60. / bpm * SAMPLE_RATE as f32;
+        let len = ((self.beats as f32 * beat_samples) as usize).max(1);
+        let mut samples = vec![0.; len];
+        for (i, note) in self.notes.iter().enumerate() {
+            let start = (note.beat * beat_samples) as usize;
+            for (j, sample) in note.sound.render(seed + i as u64).into_iter().enumerate() {
+                samples[(start + j) % len] += sample;
+            }
+        }
+        to_audio_source(&samples)
+    }
+}
+
+/// The variants of each synthesized sound.
+#[derive(Asset, TypePath, Debug, Deserialize)]
+#[serde(transparent)]
+pub struct SynthPresets(pub HashMap<SfxKey, Vec<SynthParams>>);
+
+fn to_audio_source(samples: &[f32]) -> AudioSource {
+    AudioSource {
+        bytes: encode_wav(samples).into(),
+    }
+}
+
+/// 16-bit mono PCM.
+fn encode_wav(samples: &[f32]) -> Vec<u8> {
+    let data_len = samples.len() as u32 * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    // format chunk size, PCM, channels
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    // byte rate, block align, bits per sample
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        let sample = (sample.clamp(-1., 1.) * i16::MAX as f32) as i16;
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
@@ -14,17 +14,22 @@ use crate::{
         tween::{get_relative_sprite_color_anim, get_relative_translation_tween},
     },
     math::asymptotic_smoothing_with_delta_time,
-    ui::palette::{COL_BALL, COL_BALL_FAST},
+    settings::Settings,
+    ui::palette::Palette,
     BLOOM_BASE, GAME_SIZE,
 };
 
 use super::{
     assets::ParticleAssets,
+    audio::{
+        mixer::DuckBus,
+        sfx::{PlaySfx, SfxKey},
+    },
+    beat::{BeatClock, OnBeatReflect},
     movement::{speed_factor, Homing, MoveDirection, Speed, Velocity},
-    score::Score,
     spawn::{
         ball::{Ball, InsidePaddleRadius},
-        enemy::Enemy,
+        enemy::{Enemy, EnemyKilled, KillSource},
         level::Wall,
         paddle::{Paddle, PaddleAmmo, PaddleMode, PADDLE_RADIUS},
     },
@@ -51,6 +56,10 @@ pub(super) fn plugin(app: &mut App) {
 }
 
 pub const BALL_BASE_SPEED: f32 = 250.;
+/// Extra bloom intensity on the beat.
+const BEAT_BLOOM: f32 = 0.06;
+/// Bloom intensity cap with the reduced motion on, before the bloom setting.
+const REDUCED_MOTION_MAX_BLOOM: f32 = BLOOM_BASE + 0.05;
 
 #[derive(Component, Debug, Deref, DerefMut, Reflect)]
 pub struct BallSpeed(pub f32);
@@ -61,6 +70,16 @@ impl Default for BallSpeed {
     }
 }
 
+/// Number of [`BallSpeed`] tiers above the base speed.
+pub const BALL_SPEED_TIERS: usize = 4;
+
+/// 0 at the base speed up to [`BALL_SPEED_TIERS`] at the max speed.
+pub fn ball_speed_tier(speed: f32) -> usize {
+    ((speed / BALL_BASE_SPEED) as usize)
+        .saturating_sub(1)
+        .min(BALL_SPEED_TIERS)
+}
+
 #[derive(Resource, Debug, Default, Deref, DerefMut)]
 pub struct MaxBallSpeedFactor(pub f32);
 
@@ -144,14 +163,17 @@ fn handle_ball_collisions(
         &Paddle,
         &mut PaddleMode,
     )>,
-    enemy_q: Query<&GlobalTransform, With<Enemy>>,
+    enemy_q: Query<(&GlobalTransform, &Enemy)>,
     wall_q: Query<(), With<Wall>>,
     mut cmd: Commands,
     time: Res<Time>,
     mut shake: Shakes,
     particles: Res<ParticleAssets>,
     ball_speed_factor: Res<MaxBallSpeedFactor>,
-    mut score: ResMut<Score>,
+    beat_clock: Res<BeatClock>,
+    palette: Res<Palette>,
+    mut kill_w: EventWriter<EnemyKilled>,
+    mut on_beat_w: EventWriter<OnBeatReflect>,
 ) {
     for (ball_e, ball_t, mut ball, vel, mut direction, speed, mut ball_speed) in &mut ball_q {
         if (vel.velocity() - Vec2::ZERO).length() < f32::EPSILON {
@@ -207,6 +229,7 @@ fn handle_ball_collisions(
                 // allow capturing only from the inside of the core
                 if matches!(*paddle_mode, PaddleMode::Capture) && hit_point_local.x < 0. {
                     // catching ball
+                    ball.flight_kills = 0;
                     *paddle_mode = PaddleMode::Captured {
                         shoot_rotation: Rot2::radians(angle.to_radians()),
                         ball_e,
@@ -214,14 +237,29 @@ fn handle_ball_collisions(
                     cmd.entity(ball_e)
                         .set_parent_in_place(paddle_e)
                         .insert(MovementPaused);
+                    cmd.trigger(PlaySfx::new(SfxKey::Capture));
                     cmd.entity(paddle.reflect_e)
                         .try_insert(get_relative_sprite_color_anim(
-                            paddle_mode.color(),
+                            paddle_mode.color(&palette),
                             150,
                             Some(EaseFunction::QuadraticOut),
                         ));
                 } else {
                     // reflecting ball
+                    ball.flight_kills = 0;
+                    if beat_clock.on_beat() {
+                        on_beat_w.send(OnBeatReflect {
+                            position: hit.point1,
+                        });
+                    }
+                    cmd.trigger(
+                        PlaySfx::new(SfxKey::BallReflect)
+                            .with_pitch(
+                                1. + 0.5
+                                    * speed.speed_factor(BALL_BASE_SPEED, BALL_BASE_SPEED * 3.0),
+                            )
+                            .at(hit.point1),
+                    );
                     shake.add_trauma(
                         0.15 + 0.15 * speed.speed_factor(BALL_BASE_SPEED, BALL_BASE_SPEED * 2.0),
                     );
@@ -245,6 +283,7 @@ fn handle_ball_collisions(
                         0.1 + speed.speed_factor(BALL_BASE_SPEED, BALL_BASE_SPEED * 1.5) * 0.2;
                     cmd.entity(ball_e)
                         .insert(MovementPaused::cooldown(cooldown));
+                    cmd.trigger(DuckBus::hit_stop(cooldown));
                     ball.last_reflection_time = time.elapsed_seconds() + cooldown;
 
                     // tween
@@ -271,11 +310,13 @@ fn handle_ball_collisions(
 
                 // shake
                 shake.add_trauma(0.2 + 0.125 * speed_factor);
+                cmd.trigger(PlaySfx::new(SfxKey::WallBounce).at(hit.point1));
 
                 // freeze movement
                 let cooldown = 0.085 + speed_factor * 0.125;
                 cmd.entity(ball_e)
                     .insert((MovementPaused::cooldown(cooldown), ShapecastNearestEnemy));
+                cmd.trigger(DuckBus::hit_stop(cooldown));
                 ball.last_reflection_time = time.elapsed_seconds() + cooldown;
 
                 // todo: need to fix
@@ -294,7 +335,7 @@ fn handle_ball_collisions(
                 let dir = vel.velocity().normalize_or_zero();
                 let reflect = dir - (2.0 * dir.dot(hit.normal1) * hit.normal1);
                 direction.0 = reflect;
-            } else if enemy_q.contains(hit_e) {
+            } else if let Ok((enemy_t, enemy)) = enemy_q.get(hit_e) {
                 if let Some((_, _, _, _, paddle_mode, ..)) = paddle_q.iter().next() {
                     if matches!(paddle_mode, PaddleMode::Captured { .. }) {
                         continue;
@@ -317,7 +358,16 @@ fn handle_ball_collisions(
                 let cooldown = 0.08 + speed_factor * 0.06;
                 cmd.entity(ball_e)
                     .insert((MovementPaused::cooldown(cooldown), ShapecastNearestEnemy));
-                score.0 += 1;
+                cmd.trigger(DuckBus::hit_stop(cooldown));
+                ball.flight_kills += 1;
+                kill_w.send(EnemyKilled {
+                    kind: enemy.kind,
+                    position: enemy_t.translation().truncate(),
+                    source: KillSource::Ball {
+                        speed: ball_speed.0,
+                        flight_kills: ball.flight_kills,
+                    },
+                });
             }
         }
 
@@ -339,7 +389,7 @@ fn handle_ball_collisions(
                 )
                 .iter()
             {
-                if let Ok(enemy_t) = enemy_q.get(hit.entity) {
+                if let Ok((enemy_t, _)) = enemy_q.get(hit.entity) {
                     let enemy_pos = enemy_t.translation();
                     if enemy_pos.abs().max_element() > (GAME_SIZE / 2. - 50.) {
                         // outside window
@@ -361,10 +411,11 @@ fn color_ball(
     ball_q: Query<&Ball>,
     mut sprite_q: Query<&mut Sprite>,
     factor: Res<MaxBallSpeedFactor>,
+    palette: Res<Palette>,
 ) {
     for ball in &ball_q {
         if let Ok(mut sprite) = sprite_q.get_mut(ball.sprite_e) {
-            sprite.color = lerp_color(COL_BALL, COL_BALL_FAST, factor.0);
+            sprite.color = lerp_color(palette.ball, palette.ball_fast, factor.0);
         }
     }
 }
@@ -389,19 +440,31 @@ fn update_ball_speed_factor(
 fn boost_postprocessing_based_on_ball_speed(
     factor: Res<MaxBallSpeedFactor>,
     mut bloom_q: Query<&mut BloomSettings>,
+    beat_clock: Res<BeatClock>,
+    settings: Res<Settings>,
 ) {
+    let intensity = if settings.accessibility.reduced_motion {
+        (BLOOM_BASE + 0.175 * factor.0).min(REDUCED_MOTION_MAX_BLOOM)
+    } else {
+        BLOOM_BASE + 0.175 * factor.0 + BEAT_BLOOM * beat_clock.pulse()
+    };
     for mut bloom in &mut bloom_q {
-        bloom.intensity = BLOOM_BASE + 0.175 * factor.0;
+        bloom.intensity = intensity * settings.bloom_intensity;
     }
 }
 
 fn update_trauma_based_on_ball_speed(
     factor: Res<MaxBallSpeedFactor>,
     mut shake_q: Query<&mut ShakeSettings>,
+    settings: Res<Settings>,
 ) {
     for mut shake in &mut shake_q {
         shake.decay_per_second = 0.8 + 0.35 * factor.0;
-        shake.amplitude = 35.0 - 10. * factor.0;
+        shake.amplitude = if settings.accessibility.reduced_motion {
+            0.
+        } else {
+            (35.0 - 10. * factor.0) * settings.shake_strength
+        };
     }
 }
 
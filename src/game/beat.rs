@@ -0,0 +1,124 @@
+//! The gameplay track's beat grid, followed by the spawner & the visual pulses.
+//!
+//! The clock runs on the game time rather than the audio playback, so replays
+//! see the same beats. The track restarts with every run & pauses with the game to stay in sync.
+//! The clock follows the track's [`MusicPass`], whose loops restart the stems, so the two
+//! line up again every loop, e.g. after a frame too long for the game time to keep up.
+
+use bevy::prelude::*;
+
+use crate::{screen::Screen, AppSet};
+
+use super::audio::soundtrack::{loop_soundtrack, MusicBank, MusicPass, MusicTrack, Tempo};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<Beat>()
+        .add_event::<Bar>()
+        .add_event::<OnBeatReflect>()
+        .insert_resource(BeatClock::new(default()))
+        .add_systems(OnEnter(Screen::Game), reset_beat_clock)
+        .add_systems(OnExit(Screen::Game), reset_beat_clock)
+        .add_systems(
+            Update,
+            tick_beat_clock
+                .in_set(AppSet::TickTimers)
+                .after(loop_soundtrack)
+                .run_if(in_state(Screen::Game)),
+        );
+}
+
+/// Seconds from a beat within which a reflection counts as on the beat.
+const ON_BEAT_WINDOW: f32 = 0.08;
+
+/// Sent on every beat.
+#[derive(Event, Debug)]
+pub struct Beat;
+
+/// Sent on the first beat of every bar, along with its [`Beat`].
+#[derive(Event, Debug)]
+pub struct Bar;
+
+/// A ball reflected by the paddle on the beat.
+#[derive(Event, Debug)]
+pub struct OnBeatReflect {
+    pub position: Vec2,
+}
+
+#[derive(Resource, Debug)]
+pub struct BeatClock {
+    tempo: Tempo,
+    /// Loops of the track completed.
+    loops: u32,
+    /// Seconds into the current loop.
+    elapsed: f32,
+    /// Beats sent so far.
+    beats: u32,
+}
+
+impl BeatClock {
+    fn new(tempo: Tempo) -> Self {
+        Self {
+            tempo,
+            loops: 0,
+            elapsed: 0.,
+            beats: 0,
+        }
+    }
+
+    pub fn beat_duration(&self) -> f32 {
+        self.tempo.beat_duration()
+    }
+
+    /// Beats since the first one, fractional.
+    fn position(&self) -> f32 {
+        let loop_beats = self.tempo.loop_bars.unwrap_or(0) * self.tempo.beats_per_bar;
+        (self.loops * loop_beats) as f32 + (self.elapsed - self.tempo.offset) / self.beat_duration()
+    }
+
+    /// 0 on a beat to 1 right before the next one.
+    pub fn phase(&self) -> f32 {
+        self.position().rem_euclid(1.)
+    }
+
+    /// 1 on a beat, quickly falling off toward the next one. 0 outside of a run.
+    pub fn pulse(&self) -> f32 {
+        if self.beats == 0 {
+            return 0.;
+        }
+        (1. - self.phase()).powi(4)
+    }
+
+    pub fn on_beat(&self) -> bool {
+        let phase = self.phase();
+        phase.min(1. - phase) * self.beat_duration() <= ON_BEAT_WINDOW
+    }
+}
+
+fn reset_beat_clock(mut clock: ResMut<BeatClock>, bank: Option<Res<MusicBank>>) {
+    let tempo = bank.map_or_else(default, |bank| bank.tempo(MusicTrack::Gameplay));
+    *clock = BeatClock::new(tempo);
+}
+
+fn tick_beat_clock(
+    mut clock: ResMut<BeatClock>,
+    mut beat_w: EventWriter<Beat>,
+    mut bar_w: EventWriter<Bar>,
+    pass: Res<MusicPass>,
+    time: Res<Time>,
+) {
+    if pass.track == Some(MusicTrack::Gameplay) {
+        clock.loops = pass.count;
+        clock.elapsed = pass.elapsed;
+    } else {
+        clock.elapsed += time.delta_seconds();
+    }
+
+    let passed = (clock.position().floor() + 1.).max(0.) as u32;
+    for beat in clock.beats..passed {
+        beat_w.send(Beat);
+        if beat % clock.tempo.beats_per_bar == 0 {
+            bar_w.send(Bar);
+        }
+    }
+    clock.beats = clock.beats.max(passed);
+}
@@ -2,24 +2,27 @@ use avian2d::prelude::*;
 use bevy::{prelude::*, sprite::Mesh2dHandle};
 use bevy_enoki::prelude::OneShot;
 use bevy_trauma_shake::Shakes;
-use bevy_tweening::EaseFunction;
+use bevy_tweening::{Animator, EaseFunction};
 
 use crate::{
     ext::QuatExt,
     game::{movement::Damping, tween::DespawnOnTweenCompleted},
     screen::{NextTransitionedState, Screen},
-    ui::palette::COL_GEARS_DISABLED,
+    settings::Settings,
+    ui::palette::{Palette, Tint},
 };
 
 use super::{
     assets::ParticleAssets,
+    audio::sfx::{PlaySfx, SfxKey},
+    beat::{Bar, Beat, BeatClock},
     movement::MovementPaused,
     spawn::{
         enemy::Enemy,
-        level::{AmmoFill, Core, Health, RotateWithPaddle, AMMO_FILL_RADIUS},
+        level::{AmmoFill, AmmoUi, Core, Health, RotateWithPaddle, AMMO_FILL_RADIUS},
         paddle::{PaddleAmmo, PaddleRotation},
     },
-    tween::{get_relative_scale_anim, get_relative_sprite_color_anim},
+    tween::{get_relative_scale_anim, get_relative_scale_tween, get_relative_sprite_color_anim},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -31,12 +34,19 @@ pub(super) fn plugin(app: &mut App) {
             rotate_gears,
             disable_gears,
             update_ammo_fill,
+            pulse_ammo_ui,
         ),
     );
 }
 
-#[derive(Event, Default)]
-pub struct TakenDamage;
+/// Radians the gears jump forward on the beat.
+const GEAR_BEAT_KICK: f32 = 0.06;
+
+#[derive(Event, Debug)]
+pub struct TakenDamage {
+    /// Where the enemy hit the core.
+    pub position: Vec2,
+}
 
 fn handle_collisions(
     mut core_q: Query<(&mut Health, &CollidingEntities), With<Core>>,
@@ -52,7 +62,9 @@ fn handle_collisions(
             if let Ok((enemy, enemy_t)) = enemy_q.get(*coll_e) {
                 cmd.entity(*coll_e).despawn_recursive();
                 hp.0 = hp.0.saturating_sub(1);
-                taken_dmg_w.send_default();
+                taken_dmg_w.send(TakenDamage {
+                    position: enemy_t.translation().truncate(),
+                });
                 debug!("ouch!");
                 cmd.entity(*coll_e)
                     .remove::<Enemy>()
@@ -90,11 +102,18 @@ fn rotate_gears(
         (&mut Transform, &RotateWithPaddle),
         (Without<PaddleRotation>, Without<MovementPaused>),
     >,
+    beat_clock: Res<BeatClock>,
+    settings: Res<Settings>,
 ) {
     if let Some(paddle_t) = paddle_rot_q.iter().next() {
+        let kick = if settings.accessibility.reduced_motion {
+            0.
+        } else {
+            GEAR_BEAT_KICK * beat_clock.pulse()
+        };
         for (mut gear_t, gear) in &mut gear_q {
             gear_t.rotation = Quat::from_rotation_z(
-                (gear.offset.as_radians() + paddle_t.rotation.z_angle_rad())
+                (gear.offset.as_radians() + paddle_t.rotation.z_angle_rad() + kick)
                     * (if gear.invert { 1. } else { -1. })
                     * gear.multiplier,
             );
@@ -124,6 +143,7 @@ fn disable_gears(
     mut ev_r: EventReader<TakenDamage>,
     mut core_q: Query<&mut Core>,
     mut cmd: Commands,
+    palette: Res<Palette>,
 ) {
     if let Ok(mut core) = core_q.get_single_mut() {
         for _ in ev_r.read() {
@@ -135,10 +155,47 @@ fn disable_gears(
                         350,
                         Some(bevy_tweening::EaseFunction::BackIn),
                     ),
-                    get_relative_sprite_color_anim(COL_GEARS_DISABLED, 350, None),
+                    get_relative_sprite_color_anim(palette.gears_disabled, 350, None),
+                    Tint::GearsDisabled,
                     MovementPaused,
                 ));
+                cmd.trigger(PlaySfx::new(SfxKey::GearLoss));
             }
         }
     }
 }
+
+/// Bumps the ammo UI on the beat, harder on the first beat of a bar.
+fn pulse_ammo_ui(
+    mut cmd: Commands,
+    mut beat_r: EventReader<Beat>,
+    mut bar_r: EventReader<Bar>,
+    ammo_ui_q: Query<(Entity, Option<&Animator<Transform>>), With<AmmoUi>>,
+    settings: Res<Settings>,
+) {
+    let beat = beat_r.read().count() > 0;
+    let bar = bar_r.read().count() > 0;
+    if !beat || settings.accessibility.reduced_motion {
+        return;
+    }
+    let scale = if bar { 1.12 } else { 1.06 };
+
+    for (e, animator) in &ammo_ui_q {
+        // the reload pulse & the intro take precedence
+        if animator.is_some_and(|a| a.tweenable().progress() < 1.) {
+            continue;
+        }
+        cmd.entity(e).try_insert(Animator::new(
+            get_relative_scale_tween(
+                Vec2::splat(scale).extend(1.),
+                60,
+                Some(EaseFunction::QuadraticOut),
+            )
+            .then(get_relative_scale_tween(
+                Vec3::ONE,
+                180,
+                Some(EaseFunction::QuadraticIn),
+            )),
+        ));
+    }
+}
@@ -3,30 +3,32 @@ use bevy::prelude::*;
 use bevy_enoki::prelude::*;
 use bevy_trauma_shake::Shakes;
 use bevy_tweening::{Animator, Delay, EaseFunction};
-use rand::thread_rng;
 use std::time::Duration;
 
 use crate::{
     ext::{RandExt, Vec2Ext},
-    game::{spawn::projectile::SpawnProjectile, tween::get_relative_scale_anim},
-    ui::palette::COL_ENEMY_FLASH,
+    game::{rng::GameRng, spawn::projectile::SpawnProjectile, tween::get_relative_scale_anim},
+    screen::pause::Pause,
+    settings::Settings,
+    ui::palette::Palette,
 };
 
 use super::{
     assets::ParticleAssets,
+    audio::sfx::{PlaySfx, SfxKey},
     ball::MaxBallSpeedFactor,
     input::{PlayerAction, PlayerInput},
     movement::{Damping, Impulse, MoveDirection, Speed, Velocity},
     spawn::{
-        enemy::{Enemy, Shielded},
+        enemy::{Enemy, EnemyHit, EnemyKilled, KillSource, Shielded},
         level::Health,
         paddle::{Paddle, PaddleAmmo},
         projectile::Projectile,
     },
     time::{process_cooldown, Cooldown},
     tween::{
-        delay_tween, get_relative_sprite_color_tween, get_relative_translation_tween,
-        DespawnOnTweenCompleted,
+        delay_tween, get_relative_sprite_color_anim, get_relative_sprite_color_tween,
+        get_relative_translation_tween, DespawnOnTweenCompleted,
     },
 };
 
@@ -35,7 +37,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
-            fire_gun,
+            fire_gun.run_if(not(in_state(Pause::Paused))),
             handle_collisions,
             process_cooldown::<NoAmmoShake>,
             process_cooldown::<PaddleAmmo>,
@@ -61,11 +63,11 @@ fn fire_gun(
     mut shake: Shakes,
     particles: Res<ParticleAssets>,
     ball_speed_factor: Res<MaxBallSpeedFactor>,
+    mut rng: ResMut<GameRng>,
 ) {
     if input.pressed(&PlayerAction::Shoot) {
         for (e, paddle, mut ammo, t, cooldown) in &mut ammo_q {
             if ammo.ammo() > 0 {
-                let mut rng = thread_rng();
                 let accuracy = rng.rotation_range_degrees(4.5);
                 let dir = Dir2::new(accuracy * t.right().truncate()).unwrap();
                 let rot = (accuracy * t.up().truncate()).to_quat();
@@ -77,6 +79,7 @@ fn fire_gun(
                     .with_rotation(rot),
                 });
                 ammo.offset(-1);
+                cmd.trigger(PlaySfx::new(SfxKey::GunShot));
                 shake.add_trauma(0.165 - 0.08 * ball_speed_factor.0);
                 cmd.entity(e).insert(Cooldown::<PaddleAmmo>::new(
                     0.17 - 0.08 * ball_speed_factor.0,
@@ -122,6 +125,7 @@ fn fire_gun(
                 ));
             } else if cooldown.is_none() {
                 shake.add_trauma(0.4);
+                cmd.trigger(PlaySfx::new(SfxKey::GunEmpty));
                 cmd.entity(e).insert(Cooldown::<NoAmmoShake>::new(1.));
 
                 // todo: some blinking UI or smt. to show there's no ammo
@@ -147,9 +151,14 @@ fn handle_collisions(
         &mut Impulse,
         Option<&Shielded>,
     )>,
+    texture_q: Query<&Handle<Image>>,
     mut cmd: Commands,
     time: Res<Time>,
+    settings: Res<Settings>,
+    palette: Res<Palette>,
     particles: Res<ParticleAssets>,
+    mut kill_w: EventWriter<EnemyKilled>,
+    mut hit_w: EventWriter<EnemyHit>,
 ) {
     for (e, t, projectile, vel, move_dir, speed) in &ball_q {
         if (vel.velocity() - Vec2::ZERO).length() < f32::EPSILON {
@@ -202,18 +211,52 @@ fn handle_collisions(
                         ),
                         OneShot::Despawn,
                     ));
+                    kill_w.send(EnemyKilled {
+                        kind: enemy.kind,
+                        position: enemy_t.translation().truncate(),
+                        source: KillSource::Bullet,
+                    });
                 } else {
-                    if shielded.is_none() {
+                    hit_w.send(EnemyHit {
+                        position: enemy_t.translation().truncate(),
+                        damage: if shielded.is_none() { 1 } else { 0 },
+                    });
+                    if shielded.is_none() && settings.accessibility.reduced_motion {
+                        // outline instead of a flash
+                        if let Ok(texture) = texture_q.get(enemy.sprite_e) {
+                            cmd.entity(enemy.sprite_e).with_children(|b| {
+                                b.spawn((
+                                    Name::new("Hit outline"),
+                                    SpriteBundle {
+                                        texture: texture.clone(),
+                                        sprite: Sprite {
+                                            color: palette.enemy_flash,
+                                            ..default()
+                                        },
+                                        transform: Transform::from_xyz(0., 0., -0.1)
+                                            .with_scale(Vec3::splat(1.2)),
+                                        ..default()
+                                    },
+                                    get_relative_sprite_color_anim(
+                                        palette.enemy_flash.with_alpha(0.),
+                                        250,
+                                        Some(EaseFunction::QuadraticIn),
+                                    ),
+                                    DespawnOnTweenCompleted::Itself,
+                                ));
+                            });
+                        }
+                    } else if shielded.is_none() {
                         // flash
                         cmd.entity(enemy.sprite_e).insert(Animator::new(
                             get_relative_sprite_color_tween(
-                                COL_ENEMY_FLASH,
+                                palette.enemy_flash,
                                 50,
                                 Some(EaseFunction::QuadraticIn),
                             )
                             .then(delay_tween(
                                 get_relative_sprite_color_tween(
-                                    enemy.color,
+                                    palette.enemy,
                                     50,
                                     Some(EaseFunction::QuadraticOut),
                                 ),
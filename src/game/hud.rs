@@ -0,0 +1,153 @@
+//! Heads-up display with the live score, combo, run time, threat level and ball speed tier.
+
+use bevy::prelude::*;
+use bevy_tweening::{Animator, EaseFunction};
+
+use crate::{math::asymptotic_smoothing_with_delta_time, screen::Screen, ui::prelude::*};
+
+use super::{
+    ball::{ball_speed_tier, BallSpeed, BALL_SPEED_TIERS},
+    leaderboard::format_duration,
+    score::{Combo, Score},
+    spawn::enemy::threat_level,
+    stats::RunStats,
+    tween::get_relative_scale_tween,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Game), spawn_hud)
+        .add_systems(
+            Update,
+            (
+                update_score_readout,
+                update_combo_readout,
+                update_time_readout,
+                update_threat_readout,
+                update_ball_speed_readout,
+            )
+                .run_if(in_state(Screen::Game)),
+        );
+}
+
+#[derive(Component, Debug, Default)]
+struct ScoreReadout {
+    /// Counts up to the actual score.
+    shown: f32,
+}
+
+#[derive(Component, Debug)]
+struct ComboReadout;
+
+#[derive(Component, Debug)]
+struct TimeReadout;
+
+#[derive(Component, Debug)]
+struct ThreatReadout;
+
+#[derive(Component, Debug)]
+struct BallSpeedReadout;
+
+fn spawn_hud(mut cmd: Commands) {
+    cmd.ui_root()
+        .insert((Name::new("HUD"), StateScoped(Screen::Game)))
+        .with_children(|root| {
+            // the centered square between the letterbox bars (see `resize_letterbox`)
+            root.spawn((
+                Name::new("HUD area"),
+                NodeBundle {
+                    style: Style {
+                        width: Val::Vmin(100.),
+                        height: Val::Vmin(100.),
+                        padding: UiRect::all(Val::Vmin(2.)),
+                        justify_content: JustifyContent::SpaceBetween,
+                        align_items: AlignItems::FlexStart,
+                        ..default()
+                    },
+                    ..default()
+                },
+            ))
+            .with_children(|area| {
+                area.readout("SCORE", "0").insert(ScoreReadout::default());
+                area.readout("COMBO", "x1").insert(ComboReadout);
+                area.readout("TIME", format_duration(0.))
+                    .insert(TimeReadout);
+                area.readout("THREAT", "1").insert(ThreatReadout);
+                area.readout("BALL", format!("0/{BALL_SPEED_TIERS}"))
+                    .insert(BallSpeedReadout);
+            });
+        });
+}
+
+/// Sets the readout value, skipping the text layout if it hasn't changed.
+fn set_value(text: &mut Mut<Text>, value: String) {
+    if text.sections[1].value != value {
+        text.sections[1].value = value;
+    }
+}
+
+fn update_score_readout(
+    mut cmd: Commands,
+    mut readout_q: Query<(Entity, &mut Text, &mut ScoreReadout)>,
+    score: Res<Score>,
+    time: Res<Time>,
+) {
+    for (e, mut text, mut readout) in &mut readout_q {
+        let target = score.0 as f32;
+        if score.is_changed() && target > readout.shown {
+            cmd.entity(e).try_insert(Animator::new(
+                get_relative_scale_tween(
+                    Vec2::splat(1.2).extend(1.),
+                    80,
+                    Some(EaseFunction::QuadraticOut),
+                )
+                .then(get_relative_scale_tween(
+                    Vec3::ONE,
+                    160,
+                    Some(EaseFunction::QuadraticIn),
+                )),
+            ));
+        }
+
+        readout.shown = if (target - readout.shown).abs() < 1. {
+            target
+        } else {
+            asymptotic_smoothing_with_delta_time(readout.shown, target, 0.15, time.delta_seconds())
+        };
+        set_value(&mut text, (readout.shown.round() as usize).to_string());
+    }
+}
+
+fn update_combo_readout(mut readout_q: Query<&mut Text, With<ComboReadout>>, combo: Res<Combo>) {
+    for mut text in &mut readout_q {
+        set_value(&mut text, format!("x{}", combo.multiplier()));
+    }
+}
+
+fn update_time_readout(mut readout_q: Query<&mut Text, With<TimeReadout>>, stats: Res<RunStats>) {
+    for mut text in &mut readout_q {
+        set_value(&mut text, format_duration(stats.duration));
+    }
+}
+
+fn update_threat_readout(
+    mut readout_q: Query<&mut Text, With<ThreatReadout>>,
+    stats: Res<RunStats>,
+) {
+    for mut text in &mut readout_q {
+        set_value(&mut text, (threat_level(stats.ball_kills) + 1).to_string());
+    }
+}
+
+fn update_ball_speed_readout(
+    mut readout_q: Query<&mut Text, With<BallSpeedReadout>>,
+    ball_q: Query<&BallSpeed>,
+) {
+    let tier = ball_q
+        .iter()
+        .map(|speed| ball_speed_tier(speed.0))
+        .max()
+        .unwrap_or_default();
+    for mut text in &mut readout_q {
+        set_value(&mut text, format!("{tier}/{BALL_SPEED_TIERS}"));
+    }
+}
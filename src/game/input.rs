@@ -1,12 +1,18 @@
+use std::f32::consts::TAU;
+
 use bevy::input::gamepad::GamepadEvent;
 use bevy::input::keyboard::KeyboardInput;
-use bevy::input::mouse::MouseButtonInput;
+use bevy::input::mouse::{MouseButtonInput, MouseMotion};
+use bevy::input::touch::TouchInput;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use leafwing_input_manager::plugin::InputManagerSystem;
 use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use super::{movement::HomingTarget, paddle::PADDLE_REVOLUTION_DURATION_MIN, touch::TouchAim};
 use crate::math::asymptotic_smoothing_with_delta_time;
+use crate::settings::Settings;
 use crate::AppSet;
 
 pub(super) fn plugin(app: &mut App) {
@@ -20,20 +26,34 @@ pub(super) fn plugin(app: &mut App) {
         .add_plugins(InputManagerPlugin::<PlayerAction>::default())
         .init_resource::<AimDirection>()
         .init_resource::<ActionState<PlayerAction>>()
-        .insert_resource(PlayerAction::input_map())
+        .insert_resource(PlayerAction::input_map(&KeyBindings::default()))
         .init_state::<ActiveInput>()
+        .enable_state_scoped_entities::<ActiveInput>()
         .add_systems(
             Update,
-            activate_gamepad.run_if(in_state(ActiveInput::MouseKeyboard)),
+            (
+                activate_gamepad.run_if(not(in_state(ActiveInput::Gamepad))),
+                activate_mkb.run_if(not(in_state(ActiveInput::MouseKeyboard))),
+                activate_keyboard.run_if(not(in_state(ActiveInput::Keyboard))),
+                activate_touch.run_if(not(in_state(ActiveInput::Touch))),
+            )
+                .chain(),
         )
-        .add_systems(Update, activate_mkb.run_if(in_state(ActiveInput::Gamepad)));
+        .add_systems(
+            Update,
+            apply_key_bindings.run_if(resource_changed::<Settings>),
+        );
 }
 
-#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+#[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect, Serialize, Deserialize)]
 pub enum PlayerAction {
     Shoot,
     TogglePaddleMode,
     AimGamepad,
+    /// Keyboard-only aiming, turns the paddle counterclockwise.
+    RotateLeft,
+    /// Keyboard-only aiming, turns the paddle clockwise.
+    RotateRight,
     Quit,
     Restart,
 }
@@ -41,8 +61,107 @@ pub enum PlayerAction {
 #[derive(Resource, Debug, Default, Reflect)]
 pub struct AimDirection(pub Vec2);
 
+/// A button input that can be bound to a [`PlayerAction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum BoundInput {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+}
+
+impl BoundInput {
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, BoundInput::Gamepad(_))
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            BoundInput::Key(key) => {
+                let name = format!("{key:?}");
+                name.strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name)
+                    .to_uppercase()
+            }
+            BoundInput::Mouse(MouseButton::Left) => "LMB".into(),
+            BoundInput::Mouse(MouseButton::Right) => "RMB".into(),
+            BoundInput::Mouse(MouseButton::Middle) => "MMB".into(),
+            BoundInput::Mouse(button) => format!("MOUSE {button:?}").to_uppercase(),
+            BoundInput::Gamepad(button) => format!("PAD {button:?}").to_uppercase(),
+        }
+    }
+}
+
+/// The rebindable part of the [`PlayerAction`] input map, the gamepad aim sticks are fixed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyBindings(pub Vec<(PlayerAction, BoundInput)>);
+
+impl KeyBindings {
+    pub fn inputs_of(&self, action: PlayerAction) -> impl Iterator<Item = BoundInput> + '_ {
+        self.0
+            .iter()
+            .filter(move |(a, _)| *a == action)
+            .map(|(_, input)| *input)
+    }
+
+    pub fn action_of(&self, input: BoundInput) -> Option<PlayerAction> {
+        self.0.iter().find(|(_, i)| *i == input).map(|(a, _)| *a)
+    }
+
+    /// Replaces the `old` binding of the action in place, or adds a new one without `old`.
+    pub fn rebind(&mut self, action: PlayerAction, old: Option<BoundInput>, input: BoundInput) {
+        let slot = old.and_then(|old| self.0.iter().position(|binding| *binding == (action, old)));
+        let bound = self.0.contains(&(action, input));
+        match slot {
+            Some(i) if bound => {
+                self.0.remove(i);
+            }
+            Some(i) => self.0[i] = (action, input),
+            None if bound => {}
+            None => self.0.push((action, input)),
+        }
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        use BoundInput as B;
+        use PlayerAction as A;
+
+        Self(vec![
+            // Gamepad
+            (A::Shoot, B::Gamepad(GamepadButtonType::RightTrigger)),
+            (A::Shoot, B::Gamepad(GamepadButtonType::RightTrigger2)),
+            (A::Shoot, B::Gamepad(GamepadButtonType::South)),
+            (
+                A::TogglePaddleMode,
+                B::Gamepad(GamepadButtonType::LeftTrigger),
+            ),
+            (
+                A::TogglePaddleMode,
+                B::Gamepad(GamepadButtonType::LeftTrigger2),
+            ),
+            (A::TogglePaddleMode, B::Gamepad(GamepadButtonType::West)),
+            (A::Restart, B::Gamepad(GamepadButtonType::Start)),
+            (A::Quit, B::Gamepad(GamepadButtonType::Select)),
+            // KB & Mouse
+            (A::Shoot, B::Mouse(MouseButton::Left)),
+            (A::TogglePaddleMode, B::Mouse(MouseButton::Right)),
+            (A::Quit, B::Key(KeyCode::Escape)),
+            (A::Restart, B::Key(KeyCode::KeyR)),
+            // Keyboard only
+            (A::RotateLeft, B::Key(KeyCode::KeyA)),
+            (A::RotateLeft, B::Key(KeyCode::ArrowLeft)),
+            (A::RotateRight, B::Key(KeyCode::KeyD)),
+            (A::RotateRight, B::Key(KeyCode::ArrowRight)),
+            (A::Shoot, B::Key(KeyCode::Space)),
+            (A::TogglePaddleMode, B::Key(KeyCode::ShiftLeft)),
+        ])
+    }
+}
+
 impl PlayerAction {
-    fn input_map() -> InputMap<Self> {
+    fn input_map(bindings: &KeyBindings) -> InputMap<Self> {
         let mut input_map = InputMap::default();
 
         // Gamepad
@@ -59,30 +178,28 @@ impl PlayerAction {
             Self::AimGamepad,
             DualAxis::right_stick().with_deadzone(deadzone),
         );
-        input_map.insert(Self::Shoot, GamepadButtonType::RightTrigger);
-        input_map.insert(Self::Shoot, GamepadButtonType::RightTrigger2);
-        input_map.insert(Self::Shoot, GamepadButtonType::South);
-        input_map.insert(Self::TogglePaddleMode, GamepadButtonType::LeftTrigger);
-        input_map.insert(Self::TogglePaddleMode, GamepadButtonType::LeftTrigger2);
-        input_map.insert(Self::TogglePaddleMode, GamepadButtonType::West);
-        input_map.insert(Self::Restart, GamepadButtonType::Start);
-        input_map.insert(Self::Quit, GamepadButtonType::Select);
-
-        // KB & Mouse
-        input_map.insert(Self::Shoot, MouseButton::Left);
-        input_map.insert(Self::TogglePaddleMode, MouseButton::Right);
-        input_map.insert(Self::Quit, KeyCode::Escape);
-        input_map.insert(Self::Restart, KeyCode::KeyR);
+
+        for (action, input) in &bindings.0 {
+            match *input {
+                BoundInput::Key(key) => input_map.insert(*action, key),
+                BoundInput::Mouse(button) => input_map.insert(*action, button),
+                BoundInput::Gamepad(button) => input_map.insert(*action, button),
+            };
+        }
 
         input_map
     }
 }
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
-enum ActiveInput {
+pub enum ActiveInput {
     #[default]
     MouseKeyboard,
     Gamepad,
+    /// Aiming by rotating the paddle with keys, for trackpads & accessibility.
+    Keyboard,
+    /// Aiming by dragging, with on-screen buttons.
+    Touch,
 }
 
 pub type PlayerInput<'w> = Res<'w, ActionState<PlayerAction>>;
@@ -90,16 +207,44 @@ pub type PlayerInput<'w> = Res<'w, ActionState<PlayerAction>>;
 #[derive(Resource, Default)]
 struct CursorCoords(pub Vec2);
 
-fn update_aim_direction(
+/// Half angle of the cone around the stick direction in which the aim assist picks a target.
+const AIM_ASSIST_CONE_DEG: f32 = 12.;
+
+/// Paddle rotation per pixel of mouse motion in the relative aim mode, scaled by the sensitivity.
+const RELATIVE_AIM_RADIANS_PER_PX: f32 = 0.006;
+
+pub fn update_aim_direction(
     mut aim_dir: ResMut<AimDirection>,
     input_state: Res<State<ActiveInput>>,
     cursor: Res<CursorCoords>,
+    mut motion_evr: EventReader<MouseMotion>,
     input: PlayerInput,
+    settings: Res<Settings>,
     time: Res<Time>,
+    target_q: Query<&GlobalTransform, With<HomingTarget>>,
+    // relative mouse aim the paddle hasn't caught up with yet
+    mut pending_turn: Local<f32>,
 ) {
+    // the paddle can't turn faster than this
+    let max_delta = (time.delta_seconds() / PADDLE_REVOLUTION_DURATION_MIN) * TAU;
+    let motion: f32 = motion_evr.read().map(|ev| ev.delta.x).sum();
+    let relative_aim =
+        *input_state.get() == ActiveInput::MouseKeyboard && settings.relative_mouse_aim;
+    if !relative_aim {
+        *pending_turn = 0.;
+    }
+
     aim_dir.0 = match input_state.get() {
+        ActiveInput::MouseKeyboard if relative_aim => {
+            // moving right turns clockwise, the turn is spread over the next frames so the paddle
+            // follows the motion instead of taking the shorter way around
+            *pending_turn += -motion * RELATIVE_AIM_RADIANS_PER_PX * settings.mouse_sensitivity;
+            let turn = pending_turn.clamp(-max_delta, max_delta);
+            *pending_turn -= turn;
+            Rot2::radians(turn) * aim_dir.0.normalize_or(Vec2::X)
+        }
         ActiveInput::MouseKeyboard => {
-            let deadzone_radius = 70.0;
+            let deadzone_radius = settings.aim_deadzone;
             let dist = cursor.0.length();
             if dist >= deadzone_radius {
                 cursor.0.normalize_or(aim_dir.0)
@@ -115,22 +260,68 @@ fn update_aim_direction(
         }
         ActiveInput::Gamepad => input
             .clamped_axis_pair(&PlayerAction::AimGamepad)
-            .map_or(aim_dir.0, |dir| dir.xy().normalize_or(aim_dir.0)),
+            .and_then(|dir| dir.xy().try_normalize())
+            .map_or(aim_dir.0, |dir| {
+                assist_aim(
+                    dir,
+                    settings.aim_assist,
+                    target_q.iter().map(|t| t.translation().truncate()),
+                )
+            }),
+        ActiveInput::Keyboard => {
+            // as fast as the paddle can follow
+            let turn = input.pressed(&PlayerAction::RotateLeft) as u8 as f32
+                - input.pressed(&PlayerAction::RotateRight) as u8 as f32;
+            Rot2::radians(turn * max_delta) * aim_dir.0.normalize_or(Vec2::X)
+        }
+        ActiveInput::Touch => cursor.0.normalize_or(aim_dir.0),
     }
 }
 
+/// Pulls the stick direction toward the target closest to the core inside the assist cone,
+/// harder the closer the stick already points at it.
+fn assist_aim(dir: Vec2, strength: f32, targets: impl Iterator<Item = Vec2>) -> Vec2 {
+    if strength <= 0. {
+        return dir;
+    }
+
+    let cone = AIM_ASSIST_CONE_DEG.to_radians();
+    let Some(angle) = targets
+        .filter(|pos| *pos != Vec2::ZERO)
+        .map(|pos| (pos.length_squared(), dir.angle_between(pos)))
+        .filter(|(_, angle)| angle.abs() < cone)
+        .min_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, angle)| angle)
+    else {
+        return dir;
+    };
+
+    let pull = strength * (1. - angle.abs() / cone);
+    Rot2::radians(angle * pull) * dir
+}
+
 fn update_cursor_coords(
     mut coords: ResMut<CursorCoords>,
+    input_state: Res<State<ActiveInput>>,
+    touch_aim: Res<TouchAim>,
     q_window: Query<&Window, With<PrimaryWindow>>,
     q_camera: Query<(&Camera, &GlobalTransform), With<IsDefaultUiCamera>>,
 ) {
-    let (camera, camera_transform) = q_camera.single();
-    let window = q_window.single();
+    let Ok((camera, camera_transform)) = q_camera.get_single() else {
+        return;
+    };
+    let Ok(window) = q_window.get_single() else {
+        // headless
+        return;
+    };
 
     // check the cursor is inside the window and get its position
     // then convert into world coordinates
-    if let Some(world_position) = window
-        .cursor_position()
+    let position = match input_state.get() {
+        ActiveInput::Touch => touch_aim.0,
+        _ => window.cursor_position(),
+    };
+    if let Some(world_position) = position
         .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
         .map(|ray| ray.origin.truncate())
     {
@@ -154,18 +345,51 @@ fn activate_gamepad(
     }
 }
 
-/// Switch to mouse and keyboard input when any keyboard button is pressed
+/// Switch to mouse and keyboard input when the mouse is used
+/// or any keyboard button is pressed while using a gamepad
 fn activate_mkb(
     mut next_state: ResMut<NextState<ActiveInput>>,
+    input_state: Res<State<ActiveInput>>,
     mut kb_evr: EventReader<KeyboardInput>,
     mut mouse_btn_evr: EventReader<MouseButtonInput>,
     mut cursor_evr: EventReader<CursorMoved>,
+    mut motion_evr: EventReader<MouseMotion>,
 ) {
-    if !mouse_btn_evr.is_empty() || !cursor_evr.is_empty() || !kb_evr.is_empty() {
+    let kb_used = *input_state.get() == ActiveInput::Gamepad && !kb_evr.is_empty();
+    // a locked cursor doesn't move, only the mouse motion is reported
+    let mouse_used = !mouse_btn_evr.is_empty() || !cursor_evr.is_empty() || !motion_evr.is_empty();
+    if mouse_used || kb_used {
         debug!("Switching to mouse and keyboard input");
         next_state.set(ActiveInput::MouseKeyboard);
         mouse_btn_evr.clear();
         cursor_evr.clear();
+        motion_evr.clear();
         kb_evr.clear();
     }
 }
+
+fn apply_key_bindings(settings: Res<Settings>, mut input_map: ResMut<InputMap<PlayerAction>>) {
+    *input_map = PlayerAction::input_map(&settings.key_bindings);
+}
+
+/// Switch to keyboard-only input when the paddle gets rotated with keys
+fn activate_keyboard(mut next_state: ResMut<NextState<ActiveInput>>, input: PlayerInput) {
+    if input.just_pressed(&PlayerAction::RotateLeft)
+        || input.just_pressed(&PlayerAction::RotateRight)
+    {
+        debug!("Switching to keyboard-only input");
+        next_state.set(ActiveInput::Keyboard);
+    }
+}
+
+/// Switch to touch input when the screen gets touched
+fn activate_touch(
+    mut next_state: ResMut<NextState<ActiveInput>>,
+    mut touch_evr: EventReader<TouchInput>,
+) {
+    if !touch_evr.is_empty() {
+        debug!("Switching to touch input");
+        next_state.set(ActiveInput::Touch);
+        touch_evr.clear();
+    }
+}
@@ -0,0 +1,147 @@
+use bevy::{prelude::*, utils::SystemTime};
+use serde::{Deserialize, Serialize};
+
+use crate::{screen::Screen, storage, Headless};
+
+use super::{mode::GameMode, replay::RecordedReplay, rng::GameRng, score::Score, stats::RunStats};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Leaderboard>()
+        .init_resource::<LastRunRank>()
+        .add_systems(Startup, load_leaderboard)
+        .add_systems(OnEnter(Screen::Game), reset_last_run_rank)
+        .add_systems(
+            OnEnter(Screen::GameOver),
+            record_run.run_if(not(resource_exists::<Headless>)),
+        );
+}
+
+pub const LEADERBOARD_SIZE: usize = 10;
+const LEADERBOARD_FILE: &str = "leaderboard.json";
+
+/// Best local runs, sorted from the highest score.
+#[derive(Resource, Debug, Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    entries: Vec<LeaderboardEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    pub score: usize,
+    pub seed: u64,
+    /// Seconds survived.
+    pub duration: f32,
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+    pub mode: GameMode,
+}
+
+/// Leaderboard rank of the last finished run, `None` if it didn't make the cut.
+#[derive(Resource, Debug, Default)]
+pub struct LastRunRank(pub Option<usize>);
+
+impl LastRunRank {
+    pub fn is_new_best(&self) -> bool {
+        self.0 == Some(0)
+    }
+}
+
+impl Leaderboard {
+    pub fn entries(&self) -> &[LeaderboardEntry] {
+        &self.entries
+    }
+
+    /// Inserts the entry if it makes the cut and returns its rank.
+    /// Ties are ranked below the older entries.
+    pub fn submit(&mut self, entry: LeaderboardEntry) -> Option<usize> {
+        let rank = self
+            .entries
+            .iter()
+            .position(|e| e.score < entry.score)
+            .unwrap_or(self.entries.len());
+        if rank >= LEADERBOARD_SIZE {
+            return None;
+        }
+
+        self.entries.insert(rank, entry);
+        self.entries.truncate(LEADERBOARD_SIZE);
+        Some(rank)
+    }
+
+    fn save(&self) {
+        let res = serde_json::to_string_pretty(self)
+            .map_err(|err| err.to_string())
+            .and_then(|content| storage::write(LEADERBOARD_FILE, &content));
+        if let Err(err) = res {
+            error!("failed to save the leaderboard: {err}");
+        }
+    }
+}
+
+impl LeaderboardEntry {
+    /// `yyyy-mm-dd` formatted date (UTC).
+    pub fn date_label(&self) -> String {
+        // civil from days, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = (self.timestamp / 86_400) as i64 + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        format!("{year}-{month:02}-{day:02}")
+    }
+}
+
+/// `m:ss` formatted duration.
+pub fn format_duration(duration: f32) -> String {
+    let secs = duration as u64;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+fn load_leaderboard(mut leaderboard: ResMut<Leaderboard>) {
+    match storage::read(LEADERBOARD_FILE) {
+        Ok(Some(content)) => match serde_json::from_str(&content) {
+            Ok(loaded) => *leaderboard = loaded,
+            Err(err) => warn!("ignoring a corrupt leaderboard: {err}"),
+        },
+        Ok(None) => {}
+        Err(err) => error!("failed to load the leaderboard: {err}"),
+    }
+}
+
+fn reset_last_run_rank(mut rank: ResMut<LastRunRank>) {
+    rank.0 = None;
+}
+
+pub fn record_run(
+    mut leaderboard: ResMut<Leaderboard>,
+    mut rank: ResMut<LastRunRank>,
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    rng: Res<GameRng>,
+    mode: Res<GameMode>,
+    recorded: Res<RecordedReplay>,
+) {
+    if score.0 == 0 || recorded.from_playback {
+        return;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |since_epoch| since_epoch.as_secs());
+    rank.0 = leaderboard.submit(LeaderboardEntry {
+        score: score.0,
+        seed: rng.seed(),
+        duration: stats.duration,
+        timestamp,
+        mode: *mode,
+    });
+
+    if rank.0.is_some() {
+        leaderboard.save();
+    }
+}
@@ -5,14 +5,24 @@ use bevy::prelude::*;
 pub mod assets;
 pub mod audio;
 pub mod ball;
+pub mod beat;
 mod core;
 mod gun;
+mod hud;
 pub mod input;
+pub mod leaderboard;
+pub mod mode;
 mod movement;
+pub mod online;
 pub mod paddle;
+mod popup;
+pub mod replay;
+pub mod rng;
 pub mod score;
 pub mod spawn;
+pub mod stats;
 pub mod time;
+mod touch;
 pub mod tween;
 
 pub(super) fn plugin(app: &mut App) {
@@ -29,5 +39,17 @@ pub(super) fn plugin(app: &mut App) {
         ball::plugin,
         paddle::plugin,
         score::plugin,
+        (
+            rng::plugin,
+            stats::plugin,
+            mode::plugin,
+            replay::plugin,
+            leaderboard::plugin,
+            online::plugin,
+            hud::plugin,
+            popup::plugin,
+            touch::plugin,
+            beat::plugin,
+        ),
     ));
 }
@@ -0,0 +1,48 @@
+use std::str::FromStr;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::cli::LaunchOptions;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GameMode>()
+        .register_type::<GameMode>()
+        .add_systems(Startup, apply_launch_mode);
+}
+
+/// Rules of the current run.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum GameMode {
+    /// Survive the endless swarm for as long as possible.
+    #[default]
+    Endless,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 1] = [GameMode::Endless];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Endless => "endless",
+        }
+    }
+}
+
+impl FromStr for GameMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GameMode::ALL
+            .into_iter()
+            .find(|mode| mode.name() == s)
+            .ok_or_else(|| format!("unknown game mode '{s}'"))
+    }
+}
+
+fn apply_launch_mode(options: Res<LaunchOptions>, mut mode: ResMut<GameMode>) {
+    if let Some(launch_mode) = options.mode {
+        *mode = launch_mode;
+    }
+}
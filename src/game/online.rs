@@ -0,0 +1,455 @@
+//! Optional online leaderboard: finished runs are submitted to & the top scores fetched from a service.
+//!
+//! The HTTP endpoint comes from `--leaderboard-url` or the `PORCLE_LEADERBOARD_URL` build-time variable,
+//! the game stays offline without one.
+//! Inserting an [`OnlineLeaderboard`] before `Startup` plugs in a different [`LeaderboardBackend`], e.g. a mock.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{cli::LaunchOptions, screen::Screen, Headless};
+
+use super::{leaderboard::record_run, replay::RecordedReplay, score::Score, stats::RunStats};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_online_leaderboard)
+        .add_systems(
+            OnEnter(Screen::GameOver),
+            submit_run.after(record_run).run_if(
+                resource_exists::<OnlineLeaderboard>.and_then(not(resource_exists::<Headless>)),
+            ),
+        )
+        .add_systems(
+            OnEnter(Screen::Leaderboard),
+            fetch_top_scores.run_if(resource_exists::<OnlineLeaderboard>),
+        )
+        .add_systems(
+            Update,
+            poll_responses.run_if(resource_exists::<OnlineLeaderboard>),
+        );
+}
+
+/// Number of the top scores to fetch.
+pub const ONLINE_TOP_COUNT: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnlineScore {
+    pub score: usize,
+    pub seed: u64,
+    /// Seconds survived.
+    pub duration: f32,
+    /// [`super::replay::Replay::hash`] of the run.
+    pub replay_hash: String,
+}
+
+pub type Callback<T> = Box<dyn FnOnce(Result<T, String>) + Send>;
+
+/// A service storing the online scores.
+/// The callbacks can be called from any thread.
+pub trait LeaderboardBackend: Send + Sync {
+    fn submit(&self, score: OnlineScore, on_done: Callback<()>);
+
+    fn fetch_top(&self, count: usize, on_done: Callback<Vec<OnlineScore>>);
+}
+
+/// Talks JSON to `POST {url}/scores` and `GET {url}/scores?limit={count}`.
+pub struct HttpBackend {
+    url: String,
+}
+
+impl HttpBackend {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into().trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl LeaderboardBackend for HttpBackend {
+    fn submit(&self, score: OnlineScore, on_done: Callback<()>) {
+        match ehttp::Request::json(format!("{}/scores", self.url), &score) {
+            Ok(request) => ehttp::fetch(request, move |res| {
+                on_done(res.and_then(check_status).map(|_| ()));
+            }),
+            Err(err) => on_done(Err(err.to_string())),
+        }
+    }
+
+    fn fetch_top(&self, count: usize, on_done: Callback<Vec<OnlineScore>>) {
+        let request = ehttp::Request::get(format!("{}/scores?limit={count}", self.url));
+        ehttp::fetch(request, move |res| {
+            on_done(
+                res.and_then(check_status)
+                    .and_then(|res| res.json().map_err(|err| err.to_string())),
+            );
+        });
+    }
+}
+
+fn check_status(res: ehttp::Response) -> ehttp::Result<ehttp::Response> {
+    if res.ok {
+        Ok(res)
+    } else {
+        Err(format!("{} {}", res.status, res.status_text))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub enum OnlineTop {
+    #[default]
+    NotLoaded,
+    Loading,
+    Loaded(Vec<OnlineScore>),
+    Failed(String),
+}
+
+enum Response {
+    Submitted(Result<(), String>),
+    Top(Result<Vec<OnlineScore>, String>),
+}
+
+#[derive(Resource)]
+pub struct OnlineLeaderboard {
+    backend: Box<dyn LeaderboardBackend>,
+    /// Finished requests waiting to be processed on the main thread.
+    responses: Arc<Mutex<Vec<Response>>>,
+    top: OnlineTop,
+}
+
+impl OnlineLeaderboard {
+    pub fn new(backend: impl LeaderboardBackend + 'static) -> Self {
+        Self {
+            backend: Box::new(backend),
+            responses: default(),
+            top: default(),
+        }
+    }
+
+    pub fn top(&self) -> &OnlineTop {
+        &self.top
+    }
+
+    fn respond_with<T: 'static>(&self, response: fn(Result<T, String>) -> Response) -> Callback<T> {
+        let responses = self.responses.clone();
+        Box::new(move |res| {
+            if let Ok(mut responses) = responses.lock() {
+                responses.push(response(res));
+            }
+        })
+    }
+}
+
+fn setup_online_leaderboard(
+    mut cmd: Commands,
+    options: Res<LaunchOptions>,
+    online: Option<Res<OnlineLeaderboard>>,
+) {
+    if online.is_some() {
+        // a custom backend has been plugged in
+        return;
+    }
+
+    let url = options
+        .leaderboard_url
+        .clone()
+        .or_else(|| option_env!("PORCLE_LEADERBOARD_URL").map(String::from));
+    if let Some(url) = url {
+        info!(%url, "using online leaderboard");
+        cmd.insert_resource(OnlineLeaderboard::new(HttpBackend::new(url)));
+    }
+}
+
+fn submit_run(
+    online: Res<OnlineLeaderboard>,
+    score: Res<Score>,
+    stats: Res<RunStats>,
+    recorded: Res<RecordedReplay>,
+) {
+    if score.0 == 0 || recorded.from_playback {
+        return;
+    }
+    let Some(replay) = recorded.replay() else {
+        return;
+    };
+
+    online.backend.submit(
+        OnlineScore {
+            score: score.0,
+            seed: replay.seed,
+            duration: stats.duration,
+            replay_hash: replay.hash(),
+        },
+        online.respond_with(Response::Submitted),
+    );
+}
+
+fn fetch_top_scores(mut online: ResMut<OnlineLeaderboard>) {
+    online.top = OnlineTop::Loading;
+    online
+        .backend
+        .fetch_top(ONLINE_TOP_COUNT, online.respond_with(Response::Top));
+}
+
+fn poll_responses(mut online: ResMut<OnlineLeaderboard>) {
+    let responses = match online.responses.lock() {
+        Ok(mut responses) => std::mem::take(&mut *responses),
+        Err(_) => return,
+    };
+
+    for response in responses {
+        match response {
+            Response::Submitted(Ok(())) => info!("submitted the run to the online leaderboard"),
+            Response::Submitted(Err(err)) => {
+                warn!("failed to submit the run to the online leaderboard: {err}")
+            }
+            Response::Top(Ok(scores)) => online.top = OnlineTop::Loaded(scores),
+            Response::Top(Err(err)) => {
+                warn!("failed to fetch the online leaderboard: {err}");
+                online.top = OnlineTop::Failed(err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    use bevy::ecs::system::RunSystemOnce;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::game::{mode::GameMode, replay::Replay};
+
+    /// Answers right away, keeping the submitted scores as the JSON the HTTP backend would send.
+    #[derive(Clone)]
+    struct MockBackend {
+        submitted: Arc<Mutex<Vec<Value>>>,
+        top: Result<Vec<OnlineScore>, String>,
+    }
+
+    impl MockBackend {
+        fn new(top: Result<Vec<OnlineScore>, String>) -> Self {
+            Self {
+                submitted: default(),
+                top,
+            }
+        }
+    }
+
+    impl LeaderboardBackend for MockBackend {
+        fn submit(&self, score: OnlineScore, on_done: Callback<()>) {
+            let json = serde_json::to_value(score).map_err(|err| err.to_string());
+            on_done(json.map(|json| self.submitted.lock().unwrap().push(json)));
+        }
+
+        fn fetch_top(&self, count: usize, on_done: Callback<Vec<OnlineScore>>) {
+            on_done(
+                self.top
+                    .clone()
+                    .map(|top| top.into_iter().take(count).collect()),
+            );
+        }
+    }
+
+    fn online_score(score: usize) -> OnlineScore {
+        OnlineScore {
+            score,
+            seed: 7,
+            duration: 60.,
+            replay_hash: "0123456789abcdef".into(),
+        }
+    }
+
+    fn world_with(backend: MockBackend) -> World {
+        let mut world = World::new();
+        world.insert_resource(OnlineLeaderboard::new(backend));
+        world
+    }
+
+    #[test]
+    fn submits_finished_runs() {
+        let backend = MockBackend::new(Ok(Vec::new()));
+        let mut world = world_with(backend.clone());
+        let replay = Replay {
+            version: 2,
+            seed: 42,
+            mode: GameMode::default(),
+            frames: Vec::new(),
+        };
+        let replay_hash = replay.hash();
+        world.insert_resource(Score(1234));
+        world.insert_resource(RunStats {
+            duration: 95.5,
+            ..default()
+        });
+        world.insert_resource(RecordedReplay::finished(replay));
+
+        world.run_system_once(submit_run);
+        world.run_system_once(poll_responses);
+
+        let submitted = backend.submitted.lock().unwrap();
+        assert_eq!(
+            *submitted,
+            vec![json!({
+                "score": 1234,
+                "seed": 42,
+                "duration": 95.5,
+                "replay_hash": replay_hash,
+            })]
+        );
+    }
+
+    #[test]
+    fn skips_empty_runs() {
+        let backend = MockBackend::new(Ok(Vec::new()));
+        let mut world = world_with(backend.clone());
+        world.insert_resource(Score(0));
+        world.insert_resource(RunStats::default());
+        world.insert_resource(RecordedReplay::default());
+
+        world.run_system_once(submit_run);
+
+        assert!(backend.submitted.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn fetches_the_top_scores() {
+        let scores = (0..ONLINE_TOP_COUNT + 5).rev().map(online_score).collect();
+        let mut world = world_with(MockBackend::new(Ok(scores)));
+
+        world.run_system_once(fetch_top_scores);
+        world.run_system_once(poll_responses);
+
+        let online = world.resource::<OnlineLeaderboard>();
+        let OnlineTop::Loaded(top) = online.top() else {
+            panic!("top not loaded: {:?}", online.top());
+        };
+        assert_eq!(top.len(), ONLINE_TOP_COUNT);
+        assert_eq!(top[0].score, ONLINE_TOP_COUNT + 4);
+    }
+
+    #[test]
+    fn failed_fetch_is_reported() {
+        let mut world = world_with(MockBackend::new(Err("503 Service Unavailable".into())));
+
+        world.run_system_once(fetch_top_scores);
+        assert!(matches!(
+            world.resource::<OnlineLeaderboard>().top(),
+            OnlineTop::Loading
+        ));
+        world.run_system_once(poll_responses);
+
+        assert!(matches!(
+            world.resource::<OnlineLeaderboard>().top(),
+            OnlineTop::Failed(err) if err.contains("503")
+        ));
+    }
+
+    /// Answers a single request on a loopback port, returning its URL
+    /// & the received request line with the body.
+    fn serve_once(status: &str, body: &str) -> (String, thread::JoinHandle<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            (
+                request_line.trim().to_string(),
+                String::from_utf8(body).unwrap(),
+            )
+        });
+        (url, handle)
+    }
+
+    fn wait<T: Send + 'static>(call: impl FnOnce(Callback<T>)) -> Result<T, String> {
+        let (tx, rx) = mpsc::channel();
+        call(Box::new(move |res| {
+            let _ = tx.send(res);
+        }));
+        rx.recv_timeout(Duration::from_secs(10)).unwrap()
+    }
+
+    #[test]
+    fn http_backend_posts_the_score() {
+        let (url, server) = serve_once("200 OK", "{}");
+        let backend = HttpBackend::new(format!("{url}/"));
+
+        let res = wait(|on_done| backend.submit(online_score(99), on_done));
+
+        assert_eq!(res, Ok(()));
+        let (request_line, body) = server.join().unwrap();
+        assert!(request_line.starts_with("POST /scores "), "{request_line}");
+        assert_eq!(
+            serde_json::from_str::<Value>(&body).unwrap(),
+            json!({
+                "score": 99,
+                "seed": 7,
+                "duration": 60.0,
+                "replay_hash": "0123456789abcdef",
+            })
+        );
+    }
+
+    #[test]
+    fn http_backend_fetches_the_top_scores() {
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"[{"score":30,"seed":1,"duration":12.5,"replay_hash":"a"},
+                {"score":20,"seed":2,"duration":8.0,"replay_hash":"b"}]"#,
+        );
+        let backend = HttpBackend::new(url);
+
+        let top = wait(|on_done| backend.fetch_top(2, on_done)).unwrap();
+
+        let (request_line, _) = server.join().unwrap();
+        assert!(
+            request_line.starts_with("GET /scores?limit=2 "),
+            "{request_line}"
+        );
+        let scores: Vec<_> = top.iter().map(|score| (score.score, score.seed)).collect();
+        assert_eq!(scores, vec![(30, 1), (20, 2)]);
+    }
+
+    #[test]
+    fn http_backend_reports_error_responses() {
+        let (url, server) = serve_once("500 Internal Server Error", "{}");
+        let backend = HttpBackend::new(url);
+
+        let res = wait(|on_done| backend.fetch_top(ONLINE_TOP_COUNT, on_done));
+
+        server.join().unwrap();
+        assert!(matches!(res, Err(err) if err.starts_with("500")));
+    }
+}
@@ -4,17 +4,19 @@ use std::f32::consts::TAU;
 
 use crate::{
     ext::{QuatExt, Vec2Ext},
+    ui::palette::Palette,
     AppSet,
 };
 
 use super::{
+    audio::sfx::{PlaySfx, SfxKey},
     ball::MaxBallSpeedFactor,
     input::{AimDirection, PlayerAction, PlayerInput},
     movement::{AccumulatedRotation, MoveDirection, MovementPaused},
     spawn::{
         ball::{Ball, SpawnBall},
         level::AmmoUi,
-        paddle::{Paddle, PaddleAmmo, PaddleMode, PaddleRotation},
+        paddle::{Paddle, PaddleAmmo, PaddleMode, PaddleModeIcon, PaddleRotation},
     },
     time::{process_cooldown, Cooldown},
     tween::{get_relative_scale_tween, get_relative_sprite_color_anim},
@@ -26,9 +28,10 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         (
             process_input.in_set(AppSet::ProcessInput),
-            rotate_paddle,
+            rotate_paddle.in_set(AppSet::Update),
             apply_cycle_effects,
             process_cooldown::<PaddleMode>,
+            show_paddle_mode,
         ),
     );
 }
@@ -43,6 +46,7 @@ fn process_input(
     >,
     mut cmd: Commands,
     mut ball_q: Query<&mut MoveDirection, With<Ball>>,
+    palette: Res<Palette>,
 ) {
     // todo: cooldown?
     if input.just_pressed(&PlayerAction::TogglePaddleMode) {
@@ -63,13 +67,14 @@ fn process_input(
                         cmd.entity(ball_e)
                             .remove_parent_in_place()
                             .remove::<MovementPaused>();
+                        cmd.trigger(PlaySfx::new(SfxKey::Release));
                     }
                     PaddleMode::Reflect
                 }
             };
             cmd.entity(paddle.reflect_e)
                 .try_insert(get_relative_sprite_color_anim(
-                    pm.color(),
+                    pm.color(&palette),
                     150,
                     Some(EaseFunction::QuadraticOut),
                 ));
@@ -78,10 +83,36 @@ fn process_input(
     }
 }
 
+/// Swaps the mode icon & re-tints the reflect sprite when the palette changes,
+/// mode changes tween the tint themselves.
+fn show_paddle_mode(
+    paddle_q: Query<(Ref<PaddleMode>, &Paddle)>,
+    mut icon_q: Query<(&PaddleModeIcon, &mut Visibility)>,
+    mut sprite_q: Query<&mut Sprite>,
+    palette: Res<Palette>,
+) {
+    for (mode, paddle) in &paddle_q {
+        if mode.is_changed() {
+            for (icon, mut visibility) in &mut icon_q {
+                *visibility = if *icon == mode.icon() {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                };
+            }
+        }
+        if palette.is_changed() {
+            if let Ok(mut sprite) = sprite_q.get_mut(paddle.reflect_e) {
+                sprite.color = mode.color(&palette);
+            }
+        }
+    }
+}
+
 fn rotate_paddle(
     mut rot_q: Query<&mut Transform, With<PaddleRotation>>,
     aim_dir: Res<AimDirection>,
-    time: Res<Time<Real>>,
+    time: Res<Time>,
 ) {
     for mut t in rot_q.iter_mut() {
         let current_angle = t.rotation.to_rot2();
@@ -110,8 +141,10 @@ fn apply_cycle_effects(
                 paddle_e: paddle_rot.paddle_e,
                 tween_delay_ms: 0,
             });
+            cmd.trigger(PlaySfx::new(SfxKey::Recall));
         } else if (angle.rotation - paddle_rot.ccw_start) >= 360f32.to_radians() {
             // CCW (positive angle)
+            cmd.trigger(PlaySfx::new(SfxKey::Reload));
             for mut ammo in &mut ammo_q {
                 ammo.offset(ball_speed_factor.ammo_bonus() as isize);
             }
@@ -0,0 +1,234 @@
+//! Floating damage & points text at enemy hits and kills.
+//!
+//! Popups close to each other get merged into one so hordes don't flood the world with text.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_tweening::{Animator, EaseFunction, Tween};
+
+use crate::{screen::Screen, ui::palette::Palette, AppSet};
+
+use super::{
+    score::{PointsAwarded, PointsSource},
+    spawn::enemy::{EnemyHit, KillSource},
+    tween::{
+        delay_tween, get_relative_text_color_tween, DespawnOnTweenCompleted,
+        TransformRelativeByPositionLens,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            tick_popups.in_set(AppSet::TickTimers),
+            show_popups.in_set(AppSet::Update),
+        )
+            .run_if(in_state(Screen::Game)),
+    );
+}
+
+/// Popups closer than this get merged.
+const POPUP_MERGE_RADIUS: f32 = 90.;
+/// Popups older than this don't take in new values anymore.
+const POPUP_MERGE_WINDOW: f32 = 0.4;
+/// When reached, the oldest popup gets reused.
+const POPUP_MAX_COUNT: usize = 32;
+const POPUP_RISE: f32 = 80.;
+const POPUP_RISE_MS: u64 = 800;
+const POPUP_FADE_DELAY_MS: u64 = 350;
+/// Fades out before the rise ends, which despawns the popup.
+const POPUP_FADE_MS: u64 = 350;
+const POPUP_FONT_SIZE: f32 = 36.;
+const POPUP_COMBO_FONT_SIZE: f32 = 24.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PopupKind {
+    Damage,
+    BulletPoints,
+    BallPoints,
+    OnBeatPoints,
+}
+
+impl PopupKind {
+    fn color(&self, palette: &Palette) -> Color {
+        match self {
+            PopupKind::Damage | PopupKind::BulletPoints => palette.bullet,
+            PopupKind::BallPoints => palette.ball,
+            PopupKind::OnBeatPoints => palette.paddle_reflect,
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+struct Popup {
+    kind: PopupKind,
+    value: usize,
+    combo_multiplier: u32,
+    age: f32,
+}
+
+impl Popup {
+    fn new(kind: PopupKind, value: usize, combo_multiplier: u32) -> Self {
+        Self {
+            kind,
+            value,
+            combo_multiplier,
+            age: 0.,
+        }
+    }
+
+    fn merge(&mut self, other: &Popup) {
+        self.value += other.value;
+        self.combo_multiplier = self.combo_multiplier.max(other.combo_multiplier);
+        self.age = 0.;
+    }
+
+    fn text(&self, palette: &Palette) -> Text {
+        let color = self.kind.color(palette);
+        let value = match self.kind {
+            PopupKind::Damage => format!("-{}", self.value),
+            PopupKind::BulletPoints | PopupKind::BallPoints | PopupKind::OnBeatPoints => {
+                format!("+{}", self.value)
+            }
+        };
+        let combo = if self.combo_multiplier > 1 {
+            format!(" x{}", self.combo_multiplier)
+        } else {
+            String::new()
+        };
+
+        Text::from_sections([
+            TextSection::new(
+                value,
+                TextStyle {
+                    font_size: POPUP_FONT_SIZE,
+                    color,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                combo,
+                TextStyle {
+                    font_size: POPUP_COMBO_FONT_SIZE,
+                    color,
+                    ..default()
+                },
+            ),
+        ])
+    }
+}
+
+fn tick_popups(mut popup_q: Query<&mut Popup>, time: Res<Time>) {
+    for mut popup in &mut popup_q {
+        popup.age += time.delta_seconds();
+    }
+}
+
+fn show_popups(
+    mut cmd: Commands,
+    mut hit_r: EventReader<EnemyHit>,
+    mut points_r: EventReader<PointsAwarded>,
+    mut popup_q: Query<(Entity, &mut Popup, &mut Transform, &mut Text)>,
+    palette: Res<Palette>,
+) {
+    // merge the new ones first, spawned popups only show up in the query next frame
+    let mut pending: Vec<(Popup, Vec2)> = Vec::new();
+    let hits = hit_r.read().filter(|ev| ev.damage > 0).map(|ev| {
+        (
+            Popup::new(PopupKind::Damage, ev.damage as usize, 1),
+            ev.position,
+        )
+    });
+    let points = points_r.read().map(|ev| {
+        let kind = match ev.source {
+            PointsSource::Kill(KillSource::Ball { .. }) => PopupKind::BallPoints,
+            PointsSource::Kill(KillSource::Bullet) => PopupKind::BulletPoints,
+            PointsSource::OnBeatReflect => PopupKind::OnBeatPoints,
+        };
+        (
+            Popup::new(kind, ev.points, ev.combo_multiplier),
+            ev.position,
+        )
+    });
+    for (new, position) in hits.chain(points) {
+        match pending.iter_mut().find(|(popup, pos)| {
+            popup.kind == new.kind && pos.distance(position) < POPUP_MERGE_RADIUS
+        }) {
+            Some((popup, _)) => popup.merge(&new),
+            None => pending.push((new, position)),
+        }
+    }
+
+    let mut count = popup_q.iter().len();
+    for (new, position) in pending {
+        let nearby = popup_q
+            .iter_mut()
+            .filter(|(_, popup, t, _)| {
+                popup.kind == new.kind
+                    && popup.age < POPUP_MERGE_WINDOW
+                    && t.translation.truncate().distance(position) < POPUP_MERGE_RADIUS
+            })
+            .min_by(|(_, _, a, _), (_, _, b, _)| {
+                let a = a.translation.truncate().distance_squared(position);
+                let b = b.translation.truncate().distance_squared(position);
+                a.total_cmp(&b)
+            });
+        if let Some((e, mut popup, _, mut text)) = nearby {
+            popup.merge(&new);
+            *text = popup.text(&palette);
+            animate_popup(&mut cmd, e, popup.kind, &palette);
+            continue;
+        }
+
+        if count >= POPUP_MAX_COUNT {
+            let oldest = popup_q
+                .iter_mut()
+                .max_by(|(_, a, _, _), (_, b, _, _)| a.age.total_cmp(&b.age));
+            if let Some((e, mut popup, mut t, mut text)) = oldest {
+                *popup = new;
+                *text = popup.text(&palette);
+                t.translation = position.extend(t.translation.z);
+                animate_popup(&mut cmd, e, popup.kind, &palette);
+            }
+            continue;
+        }
+
+        let kind = new.kind;
+        let e = cmd
+            .spawn((
+                Name::new("Popup"),
+                Text2dBundle {
+                    text: new.text(&palette),
+                    transform: Transform::from_translation(position.extend(20.)),
+                    ..default()
+                },
+                new,
+                DespawnOnTweenCompleted::Itself,
+                StateScoped(Screen::Game),
+            ))
+            .id();
+        animate_popup(&mut cmd, e, kind, &palette);
+        count += 1;
+    }
+}
+
+/// (Re)starts the rise & fade, the fade completing first despawns the popup.
+fn animate_popup(cmd: &mut Commands, e: Entity, kind: PopupKind, palette: &Palette) {
+    cmd.entity(e).insert((
+        Animator::new(Tween::new(
+            EaseFunction::QuadraticOut,
+            Duration::from_millis(POPUP_RISE_MS),
+            TransformRelativeByPositionLens::new(Vec3::Y * POPUP_RISE),
+        )),
+        Animator::new(delay_tween(
+            get_relative_text_color_tween(
+                kind.color(palette).with_alpha(0.),
+                POPUP_FADE_MS,
+                Some(EaseFunction::QuadraticIn),
+            ),
+            POPUP_FADE_DELAY_MS,
+        )),
+    ));
+}
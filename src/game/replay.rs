@@ -0,0 +1,260 @@
+//! Recording & playback of runs.
+//!
+//! A replay stores the seed of a run along with the frame time and the player input of every frame,
+//! so playing it back on the same build reproduces the run.
+
+use std::{fs, io, path::Path, time::Duration};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use leafwing_input_manager::{plugin::InputManagerSystem, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cli::LaunchOptions,
+    screen::{pause::Pause, Screen},
+    storage, AppSet,
+};
+
+use super::{
+    input::{update_aim_direction, AimDirection, PlayerAction},
+    mode::GameMode,
+    rng::{GameRng, RunSeed},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RecordedReplay>()
+        .add_systems(Startup, load_launch_replay)
+        .add_systems(OnEnter(Screen::Game), reset_recording)
+        .add_systems(OnExit(Screen::Game), stop_playback)
+        .add_systems(
+            PreUpdate,
+            press_replay_actions
+                .in_set(InputManagerSystem::ManualControl)
+                .run_if(resource_exists::<ReplayPlayback>),
+        )
+        .add_systems(
+            Update,
+            (
+                apply_replay_aim.run_if(resource_exists::<ReplayPlayback>),
+                record_frame.run_if(in_state(Screen::Game)),
+            )
+                .chain()
+                .in_set(AppSet::ProcessInput)
+                .after(update_aim_direction),
+        )
+        .add_systems(
+            Last,
+            advance_playback
+                .run_if(resource_exists::<ReplayPlayback>.and_then(not(in_state(Pause::Paused)))),
+        );
+}
+
+/// Bumped whenever the format changes, older replays can't be played back.
+const REPLAY_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: u64,
+    pub mode: GameMode,
+    pub frames: Vec<ReplayFrame>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    /// Frame time in nanoseconds.
+    pub delta_ns: u64,
+    pub aim: [f32; 2],
+    pub shoot: bool,
+    pub toggle_paddle_mode: bool,
+}
+
+impl Replay {
+    pub fn new(seed: u64, mode: GameMode) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            seed,
+            mode,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let replay: Self = serde_json::from_slice(&fs::read(path)?)?;
+        if replay.version != REPLAY_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported replay version {}", replay.version),
+            ));
+        }
+        Ok(replay)
+    }
+
+    /// Stores the replay next to the other game data and returns where it's been saved.
+    pub fn save(&self) -> Result<String, String> {
+        let name = format!("replays/{}-{}.json", self.seed, self.hash());
+        let content = serde_json::to_string(self).map_err(|err| err.to_string())?;
+        storage::write(&name, &content)?;
+        Ok(storage::location(&name))
+    }
+
+    /// FNV-1a hash of the serialized replay, identifies the run online.
+    pub fn hash(&self) -> String {
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+        let hash = bytes.iter().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+        });
+        format!("{hash:016x}")
+    }
+}
+
+/// Replay of the current (or last finished) run.
+#[derive(Resource, Debug, Default)]
+pub struct RecordedReplay {
+    replay: Option<Replay>,
+    /// The run is a playback of a loaded replay.
+    pub from_playback: bool,
+}
+
+impl RecordedReplay {
+    pub fn replay(&self) -> Option<&Replay> {
+        self.replay.as_ref()
+    }
+
+    #[cfg(test)]
+    pub fn finished(replay: Replay) -> Self {
+        Self {
+            replay: Some(replay),
+            from_playback: false,
+        }
+    }
+}
+
+#[derive(Resource, Debug)]
+struct ReplayPlayback {
+    replay: Replay,
+    /// Index of the frame that's being played.
+    frame: usize,
+}
+
+impl ReplayPlayback {
+    fn current(&self) -> Option<&ReplayFrame> {
+        self.replay.frames.get(self.frame)
+    }
+}
+
+fn load_launch_replay(
+    mut cmd: Commands,
+    options: Res<LaunchOptions>,
+    mut seed: ResMut<RunSeed>,
+    mut mode: ResMut<GameMode>,
+    mut time_strategy: ResMut<TimeUpdateStrategy>,
+) {
+    let Some(path) = &options.replay else {
+        return;
+    };
+
+    match Replay::load(path) {
+        Ok(replay) => {
+            info!(?path, frames = replay.frames.len(), "playing back replay");
+            seed.next = Some(replay.seed);
+            *mode = replay.mode;
+            let playback = ReplayPlayback { replay, frame: 0 };
+            if let Some(frame) = playback.current() {
+                *time_strategy =
+                    TimeUpdateStrategy::ManualDuration(Duration::from_nanos(frame.delta_ns));
+            }
+            cmd.insert_resource(playback);
+        }
+        Err(err) => error!(?path, "failed to load replay: {err}"),
+    }
+}
+
+fn reset_recording(mut recorded: ResMut<RecordedReplay>, playback: Option<Res<ReplayPlayback>>) {
+    *recorded = RecordedReplay {
+        replay: None,
+        from_playback: playback.is_some(),
+    };
+}
+
+fn record_frame(
+    mut recorded: ResMut<RecordedReplay>,
+    rng: Res<GameRng>,
+    mode: Res<GameMode>,
+    aim_dir: Res<AimDirection>,
+    input: Res<ActionState<PlayerAction>>,
+    time: Res<Time>,
+) {
+    recorded
+        .replay
+        .get_or_insert_with(|| Replay::new(rng.seed(), *mode))
+        .frames
+        .push(ReplayFrame {
+            delta_ns: time.delta().as_nanos() as u64,
+            aim: aim_dir.0.to_array(),
+            shoot: input.pressed(&PlayerAction::Shoot),
+            toggle_paddle_mode: input.just_pressed(&PlayerAction::TogglePaddleMode),
+        });
+}
+
+/// Overrides the actual input with the recorded one.
+fn press_replay_actions(
+    playback: Res<ReplayPlayback>,
+    mut action_state: ResMut<ActionState<PlayerAction>>,
+) {
+    let Some(frame) = playback.current() else {
+        return;
+    };
+
+    for (action, pressed) in [
+        (PlayerAction::Shoot, frame.shoot),
+        (PlayerAction::TogglePaddleMode, frame.toggle_paddle_mode),
+    ] {
+        action_state.release(&action);
+        if pressed {
+            action_state.press(&action);
+        }
+    }
+}
+
+fn apply_replay_aim(playback: Res<ReplayPlayback>, mut aim_dir: ResMut<AimDirection>) {
+    if let Some(frame) = playback.current() {
+        aim_dir.0 = Vec2::from_array(frame.aim);
+    }
+}
+
+/// Moves to the next frame & sets up its frame time.
+/// Until the run starts the time of the first frame is used.
+fn advance_playback(
+    mut cmd: Commands,
+    mut playback: ResMut<ReplayPlayback>,
+    mut time_strategy: ResMut<TimeUpdateStrategy>,
+    screen: Res<State<Screen>>,
+) {
+    if *screen.get() == Screen::Game {
+        playback.frame += 1;
+    }
+
+    match playback.current() {
+        Some(frame) => {
+            *time_strategy =
+                TimeUpdateStrategy::ManualDuration(Duration::from_nanos(frame.delta_ns))
+        }
+        None => {
+            info!("replay finished");
+            *time_strategy = TimeUpdateStrategy::Automatic;
+            cmd.remove_resource::<ReplayPlayback>();
+        }
+    }
+}
+
+fn stop_playback(
+    mut cmd: Commands,
+    playback: Option<Res<ReplayPlayback>>,
+    mut time_strategy: ResMut<TimeUpdateStrategy>,
+) {
+    if playback.is_some() {
+        *time_strategy = TimeUpdateStrategy::Automatic;
+        cmd.remove_resource::<ReplayPlayback>();
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+use crate::{cli::LaunchOptions, screen::Screen, settings::Settings, Headless};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RunSeed>()
+        .init_resource::<GameRng>()
+        .add_systems(Startup, apply_launch_seed)
+        .add_systems(OnEnter(Screen::Game), reseed_rng);
+}
+
+/// Picks the seed of the upcoming runs.
+#[derive(Resource, Debug, Default)]
+pub struct RunSeed {
+    /// Seed used by every run.
+    pub fixed: Option<u64>,
+    /// Seed used by the next run only, takes precedence over [`RunSeed::fixed`].
+    pub next: Option<u64>,
+}
+
+/// Seeded RNG driving everything that affects the outcome of a run.
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng {
+    seed: u64,
+    #[deref]
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(thread_rng().gen())
+    }
+}
+
+fn apply_launch_seed(options: Res<LaunchOptions>, mut seed: ResMut<RunSeed>) {
+    seed.fixed = options.seed;
+}
+
+fn reseed_rng(
+    mut seed: ResMut<RunSeed>,
+    mut rng: ResMut<GameRng>,
+    mut settings: ResMut<Settings>,
+    headless: Option<Res<Headless>>,
+) {
+    let next = seed.next.take();
+    *rng = GameRng::new(next.or(seed.fixed).unwrap_or_else(|| thread_rng().gen()));
+    debug!(seed = rng.seed(), "seeded run");
+    if settings.last_seed != Some(rng.seed()) {
+        // not a settings change, nothing needs to re-apply
+        settings.bypass_change_detection().last_seed = Some(rng.seed());
+        if headless.is_none() {
+            settings.save();
+        }
+    }
+}
@@ -1,8 +1,187 @@
 use bevy::prelude::*;
 
+use crate::{screen::Screen, AppSet};
+
+use super::{
+    ball::ball_speed_tier,
+    beat::OnBeatReflect,
+    core::TakenDamage,
+    spawn::enemy::{EnemyKilled, KillSource},
+};
+
 pub(super) fn plugin(app: &mut App) {
-    app.init_resource::<Score>();
+    app.init_resource::<Score>()
+        .init_resource::<Combo>()
+        .init_resource::<ScoreBreakdown>()
+        .add_event::<PointsAwarded>()
+        .add_systems(OnEnter(Screen::Game), reset_score)
+        .add_systems(
+            Update,
+            (
+                decay_combo.in_set(AppSet::TickTimers),
+                (break_combo, award_kills, award_on_beat_reflects)
+                    .chain()
+                    .in_set(AppSet::Update),
+            )
+                .run_if(in_state(Screen::Game)),
+        );
 }
 
+/// Time after a kill before the combo starts to decay.
+const COMBO_WINDOW: f32 = 2.0;
+/// A decaying combo loses a kill every this many seconds.
+const COMBO_DECAY_INTERVAL: f32 = 0.35;
+const COMBO_KILLS_PER_LEVEL: u32 = 3;
+const COMBO_MAX_LEVEL: u32 = 9;
+/// Ball kills get this much extra multiplier per ball speed tier.
+const BALL_SPEED_TIER_BONUS: f32 = 0.5;
+/// Max multiplier for killing several enemies in one ball flight.
+const MULTI_KILL_MAX: usize = 5;
+/// Points for reflecting the ball on the beat, multiplied by the combo.
+const ON_BEAT_REFLECT_POINTS: usize = 5;
+
 #[derive(Resource, Debug, Default, Reflect)]
 pub struct Score(pub usize);
+
+/// Quick successive kills build up the combo, which multiplies the points.
+#[derive(Resource, Debug, Default)]
+pub struct Combo {
+    pub kills: u32,
+    since_kill: f32,
+}
+
+impl Combo {
+    pub fn level(&self) -> u32 {
+        (self.kills / COMBO_KILLS_PER_LEVEL).min(COMBO_MAX_LEVEL)
+    }
+
+    pub fn multiplier(&self) -> u32 {
+        self.level() + 1
+    }
+}
+
+/// Where the points of the current (or last finished) run came from.
+/// The bonuses are the extra points on top of the [`ScoreBreakdown::base`] points.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ScoreBreakdown {
+    /// Points of the killed enemies before any multipliers.
+    pub base: usize,
+    pub combo_bonus: usize,
+    pub ball_speed_bonus: usize,
+    pub multi_kill_bonus: usize,
+    /// Points for the reflections on the beat.
+    pub rhythm_bonus: usize,
+    pub best_combo_multiplier: u32,
+    /// Most enemies killed in one ball flight.
+    pub best_multi_kill: usize,
+}
+
+#[derive(Event, Debug)]
+pub struct PointsAwarded {
+    pub points: usize,
+    pub position: Vec2,
+    pub source: PointsSource,
+    pub combo_multiplier: u32,
+}
+
+/// What the points were awarded for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointsSource {
+    Kill(KillSource),
+    OnBeatReflect,
+}
+
+fn reset_score(
+    mut score: ResMut<Score>,
+    mut combo: ResMut<Combo>,
+    mut breakdown: ResMut<ScoreBreakdown>,
+) {
+    score.0 = 0;
+    *combo = Combo::default();
+    *breakdown = ScoreBreakdown::default();
+}
+
+fn decay_combo(mut combo: ResMut<Combo>, time: Res<Time>) {
+    if combo.kills == 0 {
+        return;
+    }
+
+    combo.since_kill += time.delta_seconds();
+    if combo.since_kill >= COMBO_WINDOW + COMBO_DECAY_INTERVAL {
+        combo.kills -= 1;
+        combo.since_kill -= COMBO_DECAY_INTERVAL;
+    }
+}
+
+fn break_combo(mut combo: ResMut<Combo>, mut dmg_r: EventReader<TakenDamage>) {
+    if dmg_r.read().count() > 0 {
+        *combo = Combo::default();
+    }
+}
+
+fn award_kills(
+    mut kill_r: EventReader<EnemyKilled>,
+    mut score: ResMut<Score>,
+    mut combo: ResMut<Combo>,
+    mut breakdown: ResMut<ScoreBreakdown>,
+    mut points_w: EventWriter<PointsAwarded>,
+) {
+    for ev in kill_r.read() {
+        combo.kills += 1;
+        combo.since_kill = 0.;
+
+        let (speed_mult, multi_kill_mult) = match ev.source {
+            KillSource::Ball {
+                speed,
+                flight_kills,
+            } => {
+                breakdown.best_multi_kill = breakdown.best_multi_kill.max(flight_kills);
+                (
+                    1. + ball_speed_tier(speed) as f32 * BALL_SPEED_TIER_BONUS,
+                    flight_kills.clamp(1, MULTI_KILL_MAX),
+                )
+            }
+            KillSource::Bullet => (1., 1),
+        };
+
+        // apply the multipliers one by one to attribute the bonus points
+        let base = ev.kind.points();
+        let with_combo = base * combo.multiplier() as usize;
+        let with_speed = (with_combo as f32 * speed_mult).round() as usize;
+        let points = with_speed * multi_kill_mult;
+
+        breakdown.base += base;
+        breakdown.combo_bonus += with_combo - base;
+        breakdown.ball_speed_bonus += with_speed - with_combo;
+        breakdown.multi_kill_bonus += points - with_speed;
+        breakdown.best_combo_multiplier = breakdown.best_combo_multiplier.max(combo.multiplier());
+        score.0 += points;
+
+        points_w.send(PointsAwarded {
+            points,
+            position: ev.position,
+            source: PointsSource::Kill(ev.source),
+            combo_multiplier: combo.multiplier(),
+        });
+    }
+}
+
+fn award_on_beat_reflects(
+    mut reflect_r: EventReader<OnBeatReflect>,
+    mut score: ResMut<Score>,
+    combo: Res<Combo>,
+    mut breakdown: ResMut<ScoreBreakdown>,
+    mut points_w: EventWriter<PointsAwarded>,
+) {
+    for ev in reflect_r.read() {
+        let points = ON_BEAT_REFLECT_POINTS * combo.multiplier() as usize;
+        breakdown.rhythm_bonus += points;
+        score.0 += points;
+        points_w.send(PointsAwarded {
+            points,
+            position: ev.position,
+            source: PointsSource::OnBeatReflect,
+            combo_multiplier: combo.multiplier(),
+        });
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
         tween::{delay_tween, get_relative_scale_tween},
     },
     screen::Screen,
-    ui::palette::COL_BALL,
+    ui::palette::Palette,
 };
 
 use super::paddle::PaddleMode;
@@ -30,6 +30,8 @@ pub struct SpawnBall {
 pub struct Ball {
     pub radius: f32,
     pub last_reflection_time: f32,
+    /// Enemies killed since the ball has last left the paddle.
+    pub flight_kills: usize,
     pub sprite_e: Entity,
     pub particles_e: Entity,
 }
@@ -43,6 +45,7 @@ impl Ball {
         Self {
             radius: BALL_BASE_RADIUS,
             last_reflection_time: 0.,
+            flight_kills: 0,
             sprite_e,
             particles_e,
         }
@@ -56,6 +59,7 @@ fn spawn_ball(
     mut paddle_q: Query<&mut PaddleMode>,
     sprites: Res<SpriteAssets>,
     particles: Res<ParticleAssets>,
+    palette: Res<Palette>,
 ) {
     for e in &ball_q {
         cmd.entity(e).despawn_recursive();
@@ -69,7 +73,7 @@ fn spawn_ball(
                 SpriteBundle {
                     texture: sprites.ball.clone(),
                     sprite: Sprite {
-                        color: COL_BALL,
+                        color: palette.ball,
                         ..default()
                     },
                     transform: Transform::from_scale(Vec3::Z),
@@ -7,19 +7,32 @@ use rand::{distributions::WeightedIndex, prelude::*};
 use crate::{
     game::{
         assets::SpriteAssets,
+        beat::Beat,
         movement::{HomingTarget, MovementBundle},
-        score::Score,
+        rng::GameRng,
+        stats::RunStats,
     },
     screen::Screen,
-    ui::palette::COL_ENEMY,
-    GAME_SIZE,
+    ui::palette::{Palette, Tint},
+    AppSet, GAME_SIZE,
 };
 
 use super::level::Health;
 
 pub(super) fn plugin(app: &mut App) {
-    app.observe(spawn_enemy);
-    app.add_systems(Update, spawner.run_if(in_state(Screen::Game)));
+    app.add_event::<EnemyKilled>()
+        .add_event::<EnemyHit>()
+        .init_resource::<SpawnTimer>()
+        .init_resource::<QueuedSpawns>()
+        .observe(spawn_enemy)
+        .add_systems(OnEnter(Screen::Game), reset_spawn_timer)
+        .add_systems(
+            Update,
+            (spawner, spawn_on_beat)
+                .chain()
+                .in_set(AppSet::Update)
+                .run_if(in_state(Screen::Game)),
+        );
 }
 
 #[derive(Event, Debug)]
@@ -28,23 +41,74 @@ pub struct SpawnEnemy {
     pub position: Vec2,
 }
 
+#[derive(Event, Debug)]
+pub struct EnemyKilled {
+    pub kind: EnemyKind,
+    pub position: Vec2,
+    pub source: KillSource,
+}
+
+/// A bullet hit that didn't kill the enemy, shielded enemies take no damage.
+#[derive(Event, Debug)]
+pub struct EnemyHit {
+    pub position: Vec2,
+    pub damage: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KillSource {
+    Ball {
+        /// [`crate::game::ball::BallSpeed`] at the time of the kill.
+        speed: f32,
+        /// Kills of the ball since it has left the paddle, including this one.
+        flight_kills: usize,
+    },
+    Bullet,
+}
+
 #[derive(Component, Debug, Clone)]
 pub struct Enemy {
+    pub kind: EnemyKind,
     pub sprite_e: Entity,
-    pub color: Color,
 }
 
 #[derive(Component, Debug, Clone)]
 pub struct Shielded;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EnemyKind {
     Creepinek,
     Shieldy,
     BigBoi,
 }
 
+#[derive(Resource, Debug, Default)]
+struct SpawnTimer(Timer);
+
+/// Spawns waiting for the next beat.
+#[derive(Resource, Debug, Default)]
+struct QueuedSpawns(Vec<SpawnEnemy>);
+
 impl EnemyKind {
+    pub const ALL: [EnemyKind; 3] = [EnemyKind::Creepinek, EnemyKind::Shieldy, EnemyKind::BigBoi];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EnemyKind::Creepinek => "creepinek",
+            EnemyKind::Shieldy => "shieldy",
+            EnemyKind::BigBoi => "big_boi",
+        }
+    }
+
+    /// Points for a kill before any multipliers.
+    pub fn points(&self) -> usize {
+        match self {
+            EnemyKind::Creepinek => 10,
+            EnemyKind::Shieldy => 25,
+            EnemyKind::BigBoi => 50,
+        }
+    }
+
     fn base_speed(&self) -> f32 {
         match self {
             EnemyKind::Creepinek => 35.,
@@ -62,15 +126,39 @@ impl EnemyKind {
     }
 }
 
-fn spawner(mut cmd: Commands, mut next_timer: Local<Timer>, time: Res<Time>, score: Res<Score>) {
+/// Spawn rate tier, ramps up with the ball kills.
+pub fn threat_level(ball_kills: usize) -> usize {
+    match ball_kills {
+        0..=5 => 0,
+        6..=15 => 1,
+        16..=30 => 2,
+        31..=50 => 3,
+        51..=70 => 4,
+        71..=90 => 5,
+        91.. => 6,
+    }
+}
+
+fn reset_spawn_timer(mut next_timer: ResMut<SpawnTimer>, mut queued: ResMut<QueuedSpawns>) {
+    *next_timer = SpawnTimer::default();
+    queued.0.clear();
+}
+
+fn spawner(
+    mut queued: ResMut<QueuedSpawns>,
+    mut next_timer: ResMut<SpawnTimer>,
+    mut rng: ResMut<GameRng>,
+    time: Res<Time>,
+    stats: Res<RunStats>,
+) {
+    let next_timer = &mut next_timer.0;
     next_timer.tick(time.delta());
 
     if next_timer.just_finished() {
-        let mut rng = thread_rng();
         let spawn_dist = (2.0 * (GAME_SIZE / 2.0).powi(2)).sqrt() + 100.;
 
-        let spawnable_kinds = [EnemyKind::Creepinek, EnemyKind::Shieldy, EnemyKind::BigBoi];
-        let weights = WeightedIndex::new(match score.0 {
+        // the difficulty ramps up with the ball kills
+        let weights = WeightedIndex::new(match stats.ball_kills {
             0..=2 => [1, 0, 0],
             3..=10 => [5, 2, 0],
             11..=25 => [4, 2, 1],
@@ -78,20 +166,20 @@ fn spawner(mut cmd: Commands, mut next_timer: Local<Timer>, time: Res<Time>, sco
         })
         .expect("Create weighted index");
 
-        let kind = spawnable_kinds[weights.sample(&mut rng)];
-        cmd.trigger(SpawnEnemy {
+        let kind = EnemyKind::ALL[weights.sample(&mut **rng)];
+        queued.0.push(SpawnEnemy {
             kind,
             position: (Rot2::degrees(rng.gen_range(-360.0..360.0)) * Vec2::X).normalize()
                 * spawn_dist,
         });
-        let time_mult_range = match score.0 {
-            0..=5 => 1.0..1.3,
-            6..=15 => 0.9..1.2,
-            16..=30 => 0.8..1.1,
-            31..=50 => 0.7..1.0,
-            51..=70 => 0.5..0.8,
-            71..=90 => 0.4..0.7,
-            91.. => 0.3..0.5,
+        let time_mult_range = match threat_level(stats.ball_kills) {
+            0 => 1.0..1.3,
+            1 => 0.9..1.2,
+            2 => 0.8..1.1,
+            3 => 0.7..1.0,
+            4 => 0.5..0.8,
+            5 => 0.4..0.7,
+            _ => 0.3..0.5,
         };
         next_timer.set_duration(Duration::from_secs_f32(
             kind.base_time() * rng.gen_range(time_mult_range),
@@ -100,9 +188,27 @@ fn spawner(mut cmd: Commands, mut next_timer: Local<Timer>, time: Res<Time>, sco
     }
 }
 
-fn spawn_enemy(trigger: Trigger<SpawnEnemy>, mut cmd: Commands, sprites: Res<SpriteAssets>) {
-    let mut rng = thread_rng();
+/// The spawn times get quantised to the beats.
+fn spawn_on_beat(
+    mut cmd: Commands,
+    mut queued: ResMut<QueuedSpawns>,
+    mut beat_r: EventReader<Beat>,
+) {
+    if beat_r.read().count() == 0 {
+        return;
+    }
+    for spawn in queued.0.drain(..) {
+        cmd.trigger(spawn);
+    }
+}
 
+fn spawn_enemy(
+    trigger: Trigger<SpawnEnemy>,
+    mut cmd: Commands,
+    mut rng: ResMut<GameRng>,
+    sprites: Res<SpriteAssets>,
+    palette: Res<Palette>,
+) {
     let ev = trigger.event();
     let speed = rng.gen_range(ev.kind.base_speed()..(ev.kind.base_speed() * 1.5));
     // let speed = rng.gen_range(ev.kind.base_speed()..(ev.kind.base_speed() * 1.5)) * 5.;
@@ -115,14 +221,17 @@ fn spawn_enemy(trigger: Trigger<SpawnEnemy>, mut cmd: Commands, sprites: Res<Spr
             let c = Vec2::new(size, -size);
 
             let mesh_e = cmd
-                .spawn(SpriteBundle {
-                    texture: sprites.enemy_creepinek.clone(),
-                    sprite: Sprite {
-                        color: COL_ENEMY,
+                .spawn((
+                    SpriteBundle {
+                        texture: sprites.enemy_creepinek.clone(),
+                        sprite: Sprite {
+                            color: palette.enemy,
+                            ..default()
+                        },
                         ..default()
                     },
-                    ..default()
-                })
+                    Tint::Enemy,
+                ))
                 .id();
 
             cmd.spawn((
@@ -136,8 +245,8 @@ fn spawn_enemy(trigger: Trigger<SpawnEnemy>, mut cmd: Commands, sprites: Res<Spr
                 MovementBundle::new(-ev.position.normalize_or_zero(), speed),
                 HomingTarget,
                 Enemy {
+                    kind: ev.kind,
                     sprite_e: mesh_e,
-                    color: COL_ENEMY,
                 },
                 Health(3),
                 StateScoped(Screen::Game),
@@ -146,14 +255,17 @@ fn spawn_enemy(trigger: Trigger<SpawnEnemy>, mut cmd: Commands, sprites: Res<Spr
         }
         EnemyKind::Shieldy => {
             let mesh_e = cmd
-                .spawn(SpriteBundle {
-                    texture: sprites.enemy_creepy_shield.clone(),
-                    sprite: Sprite {
-                        color: COL_ENEMY,
+                .spawn((
+                    SpriteBundle {
+                        texture: sprites.enemy_creepy_shield.clone(),
+                        sprite: Sprite {
+                            color: palette.enemy,
+                            ..default()
+                        },
                         ..default()
                     },
-                    ..default()
-                })
+                    Tint::Enemy,
+                ))
                 .id();
 
             cmd.spawn((
@@ -167,8 +279,8 @@ fn spawn_enemy(trigger: Trigger<SpawnEnemy>, mut cmd: Commands, sprites: Res<Spr
                 MovementBundle::new(-ev.position.normalize_or_zero(), speed),
                 HomingTarget,
                 Enemy {
+                    kind: ev.kind,
                     sprite_e: mesh_e,
-                    color: COL_ENEMY,
                 },
                 Health(3),
                 Shielded,
@@ -183,14 +295,17 @@ fn spawn_enemy(trigger: Trigger<SpawnEnemy>, mut cmd: Commands, sprites: Res<Spr
             let c = Vec2::new(size, -size + 10.);
 
             let sprite_e = cmd
-                .spawn(SpriteBundle {
-                    texture: sprites.enemy_big_boi.clone(),
-                    sprite: Sprite {
-                        color: COL_ENEMY,
+                .spawn((
+                    SpriteBundle {
+                        texture: sprites.enemy_big_boi.clone(),
+                        sprite: Sprite {
+                            color: palette.enemy,
+                            ..default()
+                        },
                         ..default()
                     },
-                    ..default()
-                })
+                    Tint::Enemy,
+                ))
                 .id();
 
             cmd.spawn((
@@ -204,8 +319,8 @@ fn spawn_enemy(trigger: Trigger<SpawnEnemy>, mut cmd: Commands, sprites: Res<Spr
                 MovementBundle::new(-ev.position.normalize_or_zero(), speed),
                 HomingTarget,
                 Enemy {
+                    kind: ev.kind,
                     sprite_e,
-                    color: COL_ENEMY,
                 },
                 Health(8),
                 StateScoped(Screen::Game),
@@ -13,7 +13,7 @@ use crate::{
         tween::{delay_tween, get_relative_scale_tween},
     },
     screen::Screen,
-    ui::palette::{COL_AMMO_BG, COL_AMMO_FILL, COL_AMMO_OUT, COL_GEARS},
+    ui::palette::{Palette, Tint},
     GAME_SIZE,
 };
 
@@ -65,6 +65,7 @@ fn spawn_level(
     particles: Res<ParticleAssets>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    palette: Res<Palette>,
 ) {
     // todo: make the gears & center icons lighter/improve contrast with ball
     let cog_entity_ids: Vec<_> = (0..GEAR_COUNT)
@@ -76,7 +77,7 @@ fn spawn_level(
                 SpriteBundle {
                     texture: sprites.gear_small.clone(),
                     sprite: Sprite {
-                        color: COL_GEARS,
+                        color: palette.gears,
                         ..default()
                     },
                     transform: Transform::from_translation(((rot * Vec2::X) * 71.).extend(0.1))
@@ -89,6 +90,7 @@ fn spawn_level(
                     offset: Rot2::radians(angle),
                     multiplier: 1.0,
                 },
+                Tint::Gears,
                 Animator::new(delay_tween(
                     get_relative_scale_tween(Vec3::ONE, 400, Some(EaseFunction::BackOut)),
                     350 + i as u64 * 100,
@@ -112,6 +114,8 @@ fn spawn_level(
                 .collect(),
         },
         Health(GEAR_COUNT),
+        // ears at the arena edges, so the gameplay sounds pan by their x
+        SpatialListener::new(GAME_SIZE),
         StateScoped(Screen::Game),
     ))
     .push_children(&cog_entity_ids)
@@ -131,12 +135,13 @@ fn spawn_level(
                 SpriteBundle {
                     texture: sprites.ammo_icon.clone(),
                     sprite: Sprite {
-                        color: COL_AMMO_BG,
+                        color: palette.ammo_bg,
                         ..default()
                     },
                     transform: Transform::from_translation(Vec3::Z * 0.3),
                     ..default()
                 },
+                Tint::AmmoBg,
             ));
 
             b.spawn((
@@ -144,21 +149,23 @@ fn spawn_level(
                 AmmoFill,
                 MaterialMesh2dBundle {
                     // mesh: Mesh2dHandle(ammo_fill_handle.clone()),
-                    material: materials.add(ColorMaterial::from_color(COL_AMMO_FILL)),
+                    material: materials.add(ColorMaterial::from_color(palette.ammo_fill)),
                     transform: Transform::from_translation(Vec3::Z * 0.2)
                         .with_rotation(Quat::from_rotation_z(180f32.to_radians())),
                     ..default()
                 },
+                Tint::AmmoFill,
             ));
 
             b.spawn((
                 Name::new("ammo_bg"),
                 MaterialMesh2dBundle {
                     mesh: Mesh2dHandle(meshes.add(Circle::new(AMMO_FILL_RADIUS + 2.))),
-                    material: materials.add(ColorMaterial::from_color(COL_AMMO_OUT)),
+                    material: materials.add(ColorMaterial::from_color(palette.ammo_out)),
                     transform: Transform::from_translation(Vec3::Z * 0.1),
                     ..default()
                 },
+                Tint::AmmoOut,
             ));
         });
 
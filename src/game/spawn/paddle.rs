@@ -3,7 +3,7 @@ use std::time::Duration;
 use avian2d::prelude::*;
 use bevy::{
     prelude::*,
-    render::mesh::AnnulusMeshBuilder,
+    render::mesh::{AnnulusMeshBuilder, CircleMeshBuilder},
     sprite::{MaterialMesh2dBundle, Mesh2dHandle},
 };
 use bevy_tweening::{Animator, EaseFunction};
@@ -16,9 +16,7 @@ use crate::{
         tween::{delay_tween, get_relative_scale_tween},
     },
     screen::Screen,
-    ui::palette::{
-        COL_PADDLE, COL_PADDLE_CAPTURE, COL_PADDLE_CAPTURED, COL_PADDLE_REFLECT, COL_PADDLE_TRACKS,
-    },
+    ui::palette::{Palette, Tint},
 };
 
 use super::level::RotateWithPaddle;
@@ -52,15 +50,35 @@ pub enum PaddleMode {
 }
 
 impl PaddleMode {
-    pub fn color(&self) -> Color {
+    pub fn color(&self, palette: &Palette) -> Color {
+        match self {
+            PaddleMode::Reflect => palette.paddle_reflect,
+            PaddleMode::Capture => palette.paddle_capture,
+            PaddleMode::Captured { .. } => palette.paddle_captured,
+        }
+    }
+
+    pub fn icon(&self) -> PaddleModeIcon {
         match self {
-            PaddleMode::Reflect => COL_PADDLE_REFLECT,
-            PaddleMode::Capture => COL_PADDLE_CAPTURE,
-            PaddleMode::Captured { .. } => COL_PADDLE_CAPTURED,
+            PaddleMode::Reflect => PaddleModeIcon::Reflect,
+            PaddleMode::Capture => PaddleModeIcon::Capture,
+            PaddleMode::Captured { .. } => PaddleModeIcon::Captured,
         }
     }
 }
 
+/// Shape on the paddle telling the modes apart without relying on colour,
+/// only the current mode's one is visible.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddleModeIcon {
+    /// A bar, like the wall the ball bounces off.
+    Reflect,
+    /// An open ring waiting for the ball.
+    Capture,
+    /// A filled dot, the ball held.
+    Captured,
+}
+
 #[derive(Component, Debug)]
 pub struct PaddleRotation {
     pub cw_start: f32,
@@ -118,6 +136,7 @@ fn spawn_paddle(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     sprites: Res<SpriteAssets>,
+    palette: Res<Palette>,
 ) {
     // rails/paddle radius
     for (i, offset) in [-10., 15.].into_iter().enumerate() {
@@ -128,10 +147,11 @@ fn spawn_paddle(
             Name::new("rail"),
             MaterialMesh2dBundle {
                 mesh: Mesh2dHandle(meshes.add(annulus_builder.build())),
-                material: materials.add(ColorMaterial::from_color(COL_PADDLE_TRACKS)),
+                material: materials.add(ColorMaterial::from_color(palette.paddle_tracks)),
                 transform: Transform::zero_scale_2d(),
                 ..default()
             },
+            Tint::PaddleTracks,
             Animator::new(delay_tween(
                 get_relative_scale_tween(Vec3::ONE, 600, Some(EaseFunction::BackOut)),
                 950 + i as u64 * 150,
@@ -148,12 +168,13 @@ fn spawn_paddle(
                 SpriteBundle {
                     texture: sprites.paddle_barrel.clone(),
                     sprite: Sprite {
-                        color: COL_PADDLE,
+                        color: palette.paddle,
                         ..default()
                     },
                     transform: Transform::from_xyz(0., 55., 0.),
                     ..default()
                 },
+                Tint::Paddle,
             ));
         })
         .id();
@@ -164,7 +185,7 @@ fn spawn_paddle(
             SpriteBundle {
                 texture: sprites.paddle_reflect.clone(),
                 sprite: Sprite {
-                    color: COL_PADDLE_REFLECT,
+                    color: PaddleMode::Reflect.color(&palette),
                     ..default()
                 },
                 transform: Transform::from_xyz(0., -17.5, 0.5),
@@ -181,7 +202,7 @@ fn spawn_paddle(
                 SpriteBundle {
                     texture: sprites.paddle_base.clone(),
                     sprite: Sprite {
-                        color: COL_PADDLE,
+                        color: palette.paddle,
                         ..default()
                     },
                     transform: Transform::from_xyz(7., 0., 0.)
@@ -193,6 +214,7 @@ fn spawn_paddle(
                     get_relative_scale_tween(Vec3::ONE, 500, Some(EaseFunction::BackOut)),
                     1200,
                 )),
+                Tint::Paddle,
             ))
             .add_child(barrel_e)
             .with_children(|b| {
@@ -202,12 +224,13 @@ fn spawn_paddle(
                         SpriteBundle {
                             texture: sprites.paddle_wheel.clone(),
                             sprite: Sprite {
-                                color: COL_PADDLE,
+                                color: palette.paddle,
                                 ..default()
                             },
                             transform: Transform::from_xyz(98. * sign, -16., 0.),
                             ..default()
                         },
+                        Tint::Paddle,
                         RotateWithPaddle {
                             invert: true,
                             offset: Rot2::default(),
@@ -216,7 +239,42 @@ fn spawn_paddle(
                     ));
                 }
             })
-            .add_child(reflect_e);
+            .add_child(reflect_e)
+            .with_children(|b| {
+                let icon_material = materials.add(ColorMaterial::from_color(palette.bg));
+                let icons = [
+                    (
+                        PaddleModeIcon::Reflect,
+                        Rectangle::new(26., 7.).mesh().build(),
+                    ),
+                    (
+                        PaddleModeIcon::Capture,
+                        AnnulusMeshBuilder::new(7., 12., 24).build(),
+                    ),
+                    (
+                        PaddleModeIcon::Captured,
+                        CircleMeshBuilder::new(12., 24).build(),
+                    ),
+                ];
+                for (icon, mesh) in icons {
+                    b.spawn((
+                        Name::new("mode_icon"),
+                        MaterialMesh2dBundle {
+                            mesh: Mesh2dHandle(meshes.add(mesh)),
+                            material: icon_material.clone(),
+                            transform: Transform::from_xyz(0., 20., 0.6),
+                            visibility: if icon == PaddleModeIcon::Reflect {
+                                Visibility::Inherited
+                            } else {
+                                Visibility::Hidden
+                            },
+                            ..default()
+                        },
+                        icon,
+                        Tint::Bg,
+                    ));
+                }
+            });
         })
         .id();
 
@@ -7,7 +7,7 @@ use crate::{
         movement::{Damping, MovementBundle},
     },
     screen::Screen,
-    ui::palette::COL_BULLET,
+    ui::palette::{Palette, Tint},
 };
 
 use super::despawn::DespawnOutOfBounds;
@@ -32,20 +32,24 @@ fn spawn_projectile(
     trigger: Trigger<SpawnProjectile>,
     mut cmd: Commands,
     sprites: Res<SpriteAssets>,
+    palette: Res<Palette>,
 ) {
     let ev = trigger.event();
     let x = 16.;
     let y = 30.;
     let sprite_e = cmd
-        .spawn(SpriteBundle {
-            texture: sprites.bullet.clone(),
-            sprite: Sprite {
-                color: COL_BULLET,
+        .spawn((
+            SpriteBundle {
+                texture: sprites.bullet.clone(),
+                sprite: Sprite {
+                    color: palette.bullet,
+                    ..default()
+                },
+                transform: Transform::from_rotation(Quat::from_rotation_z(180f32.to_radians())),
                 ..default()
             },
-            transform: Transform::from_rotation(Quat::from_rotation_z(180f32.to_radians())),
-            ..default()
-        })
+            Tint::Bullet,
+        ))
         .id();
     cmd.spawn((
         Name::new("Projectile"),
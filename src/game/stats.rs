@@ -0,0 +1,136 @@
+use bevy::{prelude::*, utils::HashMap};
+use serde::Serialize;
+
+use crate::screen::Screen;
+
+use super::{
+    ball::{BallSpeed, MaxBallSpeedFactor},
+    core::TakenDamage,
+    spawn::{
+        enemy::{EnemyHit, EnemyKilled, EnemyKind, KillSource},
+        level::{Core, Health},
+        paddle::PaddleAmmo,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RunStats>()
+        .add_systems(OnEnter(Screen::Game), reset_stats)
+        .add_systems(
+            Update,
+            (track_time, track_kills, track_damage, track_ammo).run_if(in_state(Screen::Game)),
+        );
+}
+
+/// Statistics of the current (or last finished) run.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct RunStats {
+    /// Seconds survived.
+    pub duration: f32,
+    pub kills: HashMap<EnemyKind, usize>,
+    pub ball_kills: usize,
+    /// Time and remaining core health of every hit the core has taken.
+    pub core_damage: Vec<CoreDamage>,
+    /// Every shot uses one ammo, so this is also the number of shots fired.
+    pub ammo_used: usize,
+    pub ammo_gained: usize,
+    /// Shots that hit an enemy.
+    pub shots_hit: usize,
+    pub max_ball_speed: f32,
+    ball_speed_factor_sum: f32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct CoreDamage {
+    pub time: f32,
+    pub health: u8,
+}
+
+impl RunStats {
+    pub fn kills_of(&self, kind: EnemyKind) -> usize {
+        self.kills.get(&kind).copied().unwrap_or_default()
+    }
+
+    pub fn total_kills(&self) -> usize {
+        self.kills.values().sum()
+    }
+
+    /// Time-weighted average of [`MaxBallSpeedFactor`].
+    pub fn avg_ball_speed_factor(&self) -> f32 {
+        if self.duration > 0. {
+            self.ball_speed_factor_sum / self.duration
+        } else {
+            0.
+        }
+    }
+}
+
+fn reset_stats(mut stats: ResMut<RunStats>) {
+    *stats = RunStats::default();
+}
+
+fn track_time(
+    mut stats: ResMut<RunStats>,
+    core_q: Query<&Health, With<Core>>,
+    ball_speed_factor: Res<MaxBallSpeedFactor>,
+    ball_q: Query<&BallSpeed>,
+    time: Res<Time>,
+) {
+    if core_q.iter().any(|hp| hp.0 == 0) {
+        // the core is gone, the run is just waiting for the game over transition
+        return;
+    }
+
+    stats.duration += time.delta_seconds();
+    stats.ball_speed_factor_sum += ball_speed_factor.0 * time.delta_seconds();
+    for speed in &ball_q {
+        stats.max_ball_speed = stats.max_ball_speed.max(speed.0);
+    }
+}
+
+fn track_kills(
+    mut stats: ResMut<RunStats>,
+    mut kill_r: EventReader<EnemyKilled>,
+    mut hit_r: EventReader<EnemyHit>,
+) {
+    for ev in kill_r.read() {
+        *stats.kills.entry(ev.kind).or_default() += 1;
+        match ev.source {
+            KillSource::Ball { .. } => stats.ball_kills += 1,
+            KillSource::Bullet => stats.shots_hit += 1,
+        }
+    }
+    stats.shots_hit += hit_r.read().count();
+}
+
+fn track_damage(
+    mut stats: ResMut<RunStats>,
+    mut dmg_r: EventReader<TakenDamage>,
+    core_q: Query<&Health, With<Core>>,
+) {
+    let health = core_q.iter().next().map_or(0, |hp| hp.0);
+    for _ in dmg_r.read() {
+        let time = stats.duration;
+        stats.core_damage.push(CoreDamage { time, health });
+    }
+}
+
+fn track_ammo(
+    mut stats: ResMut<RunStats>,
+    ammo_q: Query<Ref<PaddleAmmo>>,
+    mut prev_ammo: Local<usize>,
+) {
+    for ammo in &ammo_q {
+        if ammo.is_added() {
+            *prev_ammo = ammo.ammo();
+        } else if ammo.is_changed() {
+            let current = ammo.ammo();
+            if current > *prev_ammo {
+                stats.ammo_gained += current - *prev_ammo;
+            } else {
+                stats.ammo_used += *prev_ammo - current;
+            }
+            *prev_ammo = current;
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Touch controls for the mobile web build: dragging anywhere aims,
+//! the on-screen buttons fire & toggle the capture mode.
+
+use bevy::prelude::*;
+use leafwing_input_manager::{plugin::InputManagerSystem, prelude::*};
+
+use crate::{screen::Screen, ui::prelude::*};
+
+use super::input::{ActiveInput, PlayerAction};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<TouchAim>()
+        .add_systems(
+            OnEnter(Screen::Game),
+            spawn_touch_controls.run_if(in_state(ActiveInput::Touch)),
+        )
+        .add_systems(
+            OnEnter(ActiveInput::Touch),
+            spawn_touch_controls.run_if(in_state(Screen::Game)),
+        )
+        .add_systems(
+            PreUpdate,
+            read_touches
+                .in_set(InputManagerSystem::ManualControl)
+                .run_if(in_state(Screen::Game).and_then(in_state(ActiveInput::Touch))),
+        );
+}
+
+/// Window position of the touch that aims, if any.
+#[derive(Resource, Debug, Default)]
+pub struct TouchAim(pub Option<Vec2>);
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum TouchButton {
+    Fire,
+    Capture,
+}
+
+fn spawn_touch_controls(mut cmd: Commands) {
+    cmd.ui_root()
+        .insert((
+            Name::new("Touch controls"),
+            StateScoped(Screen::Game),
+            StateScoped(ActiveInput::Touch),
+        ))
+        .with_children(|root| {
+            root.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::FlexEnd,
+                    padding: UiRect::all(Val::Px(24.)),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|children| {
+                spawn_touch_button(children, "CAPTURE", TouchButton::Capture);
+                spawn_touch_button(children, "FIRE", TouchButton::Fire);
+            });
+        });
+}
+
+/// Styled like a button, but not a [`Button`], so taps don't click or navigate the UI.
+fn spawn_touch_button(children: &mut ChildBuilder, text: &str, button: TouchButton) {
+    children
+        .spawn((
+            Name::new("Touch button"),
+            NodeBundle {
+                style: Style {
+                    min_width: Val::Px(200.),
+                    height: Val::Px(120.),
+                    padding: UiRect::horizontal(Val::Px(20.)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(ui_palette::BUTTON_BG),
+                border_radius: BorderRadius::all(Val::Px(12.)),
+                ..default()
+            },
+            button,
+        ))
+        .with_children(|children| {
+            children.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size: 40.,
+                    color: ui_palette::BUTTON_TEXT,
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// Every touch keeps the role given by where it started, so one finger can aim while another fires.
+fn read_touches(
+    touches: Res<Touches>,
+    button_q: Query<(&Node, &GlobalTransform, &TouchButton)>,
+    mut aim: ResMut<TouchAim>,
+    mut action_state: ResMut<ActionState<PlayerAction>>,
+) {
+    let button_at = |position: Vec2| {
+        button_q
+            .iter()
+            .find(|(node, t, _)| node.logical_rect(t).contains(position))
+            .map(|(.., button)| *button)
+    };
+
+    aim.0 = None;
+    for touch in touches.iter() {
+        match button_at(touch.start_position()) {
+            Some(TouchButton::Fire) => action_state.press(&PlayerAction::Shoot),
+            Some(TouchButton::Capture) => {}
+            None => {
+                if aim.0.is_none() {
+                    aim.0 = Some(touch.position());
+                }
+            }
+        }
+    }
+
+    if touches
+        .iter_just_pressed()
+        .any(|touch| button_at(touch.start_position()) == Some(TouchButton::Capture))
+    {
+        action_state.press(&PlayerAction::TogglePaddleMode);
+    }
+}
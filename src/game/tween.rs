@@ -39,7 +39,11 @@ impl<T: Send + Sync> TweenFactor<T> {
     }
 }
 
-pub fn tween_factor<T: Send + Sync>(mut factor_q: Query<&mut TweenFactor<T>>, time: Res<Time>) {
+/// Ticks in real time, so it keeps going while the game is paused.
+pub fn tween_factor<T: Send + Sync>(
+    mut factor_q: Query<&mut TweenFactor<T>>,
+    time: Res<Time<Real>>,
+) {
     for mut factor in &mut factor_q {
         if let Some(delay) = factor.delay.as_mut() {
             delay.tick(time.delta());
@@ -161,7 +165,6 @@ pub struct TransformRelativeByPositionLens {
 }
 
 impl TransformRelativeByPositionLens {
-    #[allow(dead_code)]
     pub fn new(move_by: Vec3) -> Self {
         Self {
             move_by,
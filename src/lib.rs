@@ -1,21 +1,39 @@
+pub mod balance;
+pub mod cli;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod ext;
 mod game;
 mod math;
 mod screen;
+mod settings;
+mod storage;
 mod ui;
 
+use std::time::Duration;
+
 use bevy::{
+    app::ScheduleRunnerPlugin,
     asset::AssetMetaCheck,
-    audio::{AudioPlugin, Volume},
     core_pipeline::bloom::{BloomCompositeMode, BloomSettings},
     prelude::*,
-    render::camera::ScalingMode,
+    render::{camera::ScalingMode, settings::WgpuSettings, RenderPlugin},
+    window::ExitCondition,
+    winit::WinitPlugin,
 };
 use bevy_trauma_shake::ShakeSettings;
+use cli::LaunchOptions;
+
+#[derive(Default)]
+pub struct AppPlugin {
+    /// Run without a window or a renderer, e.g. for [`balance`] simulations.
+    pub headless: bool,
+    pub options: LaunchOptions,
+}
 
-pub struct AppPlugin;
+/// Marks apps running with [`AppPlugin::headless`].
+#[derive(Resource, Debug)]
+pub struct Headless;
 
 pub const GAME_SIZE: f32 = 1600.;
 pub const MIN_WINDOW_SIZE: f32 = 720.;
@@ -29,48 +47,65 @@ impl Plugin for AppPlugin {
             (AppSet::TickTimers, AppSet::ProcessInput, AppSet::Update).chain(),
         );
 
+        app.insert_resource(self.options.clone());
+
         // Spawn the main camera.
         app.add_systems(Startup, spawn_camera);
 
         // Add Bevy plugins.
-        app.add_plugins(
-            DefaultPlugins
-                .set(AssetPlugin {
-                    // Wasm builds will check for meta files (that don't exist) if this isn't set.
-                    // This causes errors and even panics on web build on itch.
-                    // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
-                    meta_check: AssetMetaCheck::Never,
-                    ..default()
-                })
+        let mut default_plugins = DefaultPlugins
+            .set(AssetPlugin {
+                // Wasm builds will check for meta files (that don't exist) if this isn't set.
+                // This causes errors and even panics on web build on itch.
+                // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
+                meta_check: AssetMetaCheck::Never,
+                ..default()
+            })
+            // pixelart
+            .set(ImagePlugin::default_linear());
+        if self.headless {
+            app.insert_resource(Headless);
+            default_plugins = default_plugins
                 .set(WindowPlugin {
-                    primary_window: Window {
-                        title: "Porcle".to_string(),
-                        canvas: Some("#bevy".to_string()),
-                        fit_canvas_to_parent: true,
-                        resolution: Vec2::splat(1024.).into(),
-                        resize_constraints: WindowResizeConstraints {
-                            min_width: MIN_WINDOW_SIZE,
-                            min_height: MIN_WINDOW_SIZE,
-                            ..default()
-                        },
-                        prevent_default_event_handling: true,
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    close_when_requested: false,
+                })
+                .set(RenderPlugin {
+                    render_creation: WgpuSettings {
+                        backends: None,
                         ..default()
                     }
                     .into(),
                     ..default()
                 })
-                // pixelart
-                .set(ImagePlugin::default_linear())
-                .set(AudioPlugin {
-                    global_volume: GlobalVolume {
-                        volume: Volume::new(0.3),
+                .disable::<WinitPlugin>();
+            // no winit event loop, so tick the schedules as fast as possible
+            app.add_plugins(ScheduleRunnerPlugin::run_loop(Duration::ZERO));
+        } else {
+            default_plugins = default_plugins.set(WindowPlugin {
+                primary_window: Window {
+                    title: "Porcle".to_string(),
+                    canvas: Some("#bevy".to_string()),
+                    fit_canvas_to_parent: true,
+                    resolution: self.options.resolution.unwrap_or(Vec2::splat(1024.)).into(),
+                    mode: self.options.window_mode.unwrap_or_default(),
+                    resize_constraints: WindowResizeConstraints {
+                        min_width: MIN_WINDOW_SIZE,
+                        min_height: MIN_WINDOW_SIZE,
+                        ..default()
                     },
+                    prevent_default_event_handling: true,
                     ..default()
-                }),
-        );
+                }
+                .into(),
+                ..default()
+            });
+        }
+        app.add_plugins(default_plugins);
 
         // Add project plugins.
-        app.add_plugins((game::plugin, screen::plugin, ui::plugin));
+        app.add_plugins((game::plugin, screen::plugin, settings::plugin, ui::plugin));
 
         // Add external plugins
         app.add_plugins((
@@ -81,7 +116,9 @@ impl Plugin for AppPlugin {
 
         // Enable dev tools for dev builds.
         #[cfg(feature = "dev")]
-        app.add_plugins(dev_tools::plugin);
+        if !self.headless {
+            app.add_plugins(dev_tools::plugin);
+        }
     }
 }
 
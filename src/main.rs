@@ -2,8 +2,28 @@
 #![cfg_attr(not(feature = "dev"), windows_subsystem = "windows")]
 
 use bevy::prelude::*;
-use porcle::AppPlugin;
+use porcle::{
+    cli::{ArgsError, LaunchOptions},
+    AppPlugin,
+};
 
 fn main() -> AppExit {
-    App::new().add_plugins(AppPlugin).run()
+    let options = match LaunchOptions::from_args() {
+        Ok(options) => options,
+        Err(ArgsError::Help) => {
+            println!("{}", LaunchOptions::USAGE);
+            return AppExit::Success;
+        }
+        Err(ArgsError::Invalid(err)) => {
+            eprintln!("{err}\n\n{}", LaunchOptions::USAGE);
+            return AppExit::error();
+        }
+    };
+
+    App::new()
+        .add_plugins(AppPlugin {
+            options,
+            ..default()
+        })
+        .run()
 }
@@ -0,0 +1,274 @@
+//! Rebinding of the player actions, reachable from the settings.
+
+use bevy::{input::InputSystem, prelude::*, ui::UiSystem};
+use leafwing_input_manager::plugin::InputManagerSystem;
+
+use super::{pause::PauseMenu, NextTransitionedState, Screen};
+use crate::{
+    game::input::{BoundInput, KeyBindings, PlayerAction},
+    settings::Settings,
+    ui::{navigation::NavigationSystem, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Capture>()
+        .add_systems(OnEnter(Screen::Controls), enter_controls)
+        // the captured input mustn't also press buttons, navigate or trigger its action
+        .add_systems(
+            PreUpdate,
+            capture_binding
+                .after(InputSystem)
+                .before(InputManagerSystem::Update)
+                .before(UiSystem::Focus)
+                .before(NavigationSystem)
+                .run_if(
+                    any_with_component::<BindingsList>
+                        .and_then(|capture: Res<Capture>| capture.action.is_some()),
+                ),
+        )
+        .add_systems(
+            Update,
+            (reset_capture, handle_controls_action, update_bindings_list).chain(),
+        )
+        .register_type::<ControlsAction>();
+}
+
+const REBINDABLE_ACTIONS: [PlayerAction; 6] = [
+    PlayerAction::Shoot,
+    PlayerAction::TogglePaddleMode,
+    PlayerAction::RotateLeft,
+    PlayerAction::RotateRight,
+    PlayerAction::Quit,
+    PlayerAction::Restart,
+];
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum ControlsAction {
+    /// Replaces the binding, or adds one without it.
+    Rebind(PlayerAction, Option<BoundInput>),
+    /// Stops waiting for an input, so every key can be bound.
+    Cancel,
+    Reset,
+    Back,
+}
+
+/// The action waiting for a new input & the outcome of the last rebind.
+#[derive(Resource, Debug, Default)]
+struct Capture {
+    action: Option<PlayerAction>,
+    /// The binding being replaced.
+    slot: Option<BoundInput>,
+    status: String,
+}
+
+#[derive(Component)]
+struct BindingsList;
+
+fn action_name(action: PlayerAction) -> &'static str {
+    match action {
+        PlayerAction::Shoot => "SHOOT",
+        PlayerAction::TogglePaddleMode => "PADDLE MODE",
+        PlayerAction::AimGamepad => "AIM",
+        PlayerAction::RotateLeft => "TURN LEFT",
+        PlayerAction::RotateRight => "TURN RIGHT",
+        PlayerAction::Quit => "PAUSE",
+        PlayerAction::Restart => "RESTART",
+    }
+}
+
+fn enter_controls(mut commands: Commands) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Controls))
+        .with_children(spawn_controls_menu);
+}
+
+/// Spawns the controls widgets, also used by the pause menu.
+pub(super) fn spawn_controls_menu(children: &mut ChildBuilder) {
+    children.header("CONTROLS");
+    children.label("AIM: MOUSE / PAD STICKS");
+    children.spawn((
+        Name::new("Bindings list"),
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.),
+                ..default()
+            },
+            ..default()
+        },
+        BindingsList,
+    ));
+    children
+        .button("RESET TO DEFAULTS")
+        .insert(ControlsAction::Reset);
+    children
+        .button("BACK")
+        .insert((ControlsAction::Back, BackButton));
+}
+
+fn reset_capture(list_q: Query<(), Added<BindingsList>>, mut capture: ResMut<Capture>) {
+    if !list_q.is_empty() {
+        *capture = Capture::default();
+    }
+}
+
+fn capture_binding(
+    mut capture: ResMut<Capture>,
+    mut settings: ResMut<Settings>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+    mut gamepad_buttons: ResMut<ButtonInput<GamepadButton>>,
+    button_q: Query<&Interaction, With<ControlsAction>>,
+) {
+    let Some(action) = capture.action else {
+        return;
+    };
+    // clicks on the buttons are left to them, e.g. to cancel
+    let over_button = button_q.iter().any(|i| *i != Interaction::None);
+
+    let key = keys.get_just_pressed().next().copied();
+    let mouse_button = mouse
+        .get_just_pressed()
+        .next()
+        .copied()
+        .filter(|_| !over_button);
+    let gamepad_button = gamepad_buttons.get_just_pressed().next().copied();
+    // reset rather than clearing `just_pressed`, the action state follows the held inputs
+    let input = if let Some(key) = key {
+        keys.reset(key);
+        BoundInput::Key(key)
+    } else if let Some(button) = mouse_button {
+        mouse.reset(button);
+        BoundInput::Mouse(button)
+    } else if let Some(button) = gamepad_button {
+        gamepad_buttons.reset(button);
+        BoundInput::Gamepad(button.button_type)
+    } else {
+        return;
+    };
+
+    capture.action = None;
+    let slot = capture.slot.take();
+    capture.status = match settings.key_bindings.action_of(input) {
+        Some(other) if other != action => format!(
+            "{} IS ALREADY BOUND TO {}",
+            input.label(),
+            action_name(other)
+        ),
+        _ => {
+            settings.key_bindings.rebind(action, slot, input);
+            String::new()
+        }
+    };
+}
+
+fn handle_controls_action(
+    mut capture: ResMut<Capture>,
+    mut settings: ResMut<Settings>,
+    mut next_screen: ResMut<NextTransitionedState>,
+    mut next_pause_menu: Option<ResMut<NextState<PauseMenu>>>,
+    screen: Res<State<Screen>>,
+    mut button_query: InteractionQuery<&ControlsAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                ControlsAction::Rebind(action, slot) => {
+                    capture.action = Some(*action);
+                    capture.slot = *slot;
+                    capture.status = "PRESS A KEY OR BUTTON".into();
+                }
+                ControlsAction::Cancel => {
+                    *capture = Capture {
+                        status: "REBINDING CANCELLED".into(),
+                        ..default()
+                    };
+                }
+                ControlsAction::Reset => {
+                    settings.key_bindings = KeyBindings::default();
+                    capture.status = "CONTROLS RESET".into();
+                }
+                ControlsAction::Back => match (screen.get(), next_pause_menu.as_mut()) {
+                    (Screen::Game, Some(next_pause_menu)) => {
+                        next_pause_menu.set(PauseMenu::Settings)
+                    }
+                    _ => next_screen.set(Screen::Settings),
+                },
+            }
+        }
+    }
+}
+
+fn update_bindings_list(
+    mut cmd: Commands,
+    list_q: Query<(Entity, Ref<BindingsList>)>,
+    settings: Res<Settings>,
+    capture: Res<Capture>,
+) {
+    for (list_e, list) in &list_q {
+        if !list.is_added() && !settings.is_changed() && !capture.is_changed() {
+            continue;
+        }
+
+        cmd.entity(list_e)
+            .despawn_descendants()
+            .with_children(|children| {
+                children
+                    .spawn(NodeBundle {
+                        style: Style {
+                            max_width: Val::Px(900.),
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: Val::Px(12.),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|children| spawn_binding_buttons(children, &settings, &capture));
+                children.label(capture.status.clone());
+                if capture.action.is_some() {
+                    children.button("CANCEL").insert(ControlsAction::Cancel);
+                }
+            });
+    }
+}
+
+fn spawn_binding_buttons(children: &mut ChildBuilder, settings: &Settings, capture: &Capture) {
+    for action in REBINDABLE_ACTIONS {
+        children
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(12.),
+                    row_gap: Val::Px(12.),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|children| {
+                children.label(format!("{}:", action_name(action)));
+                // a slot per binding & one adding a binding
+                let slots = settings
+                    .key_bindings
+                    .inputs_of(action)
+                    .map(Some)
+                    .chain([None]);
+                for slot in slots {
+                    let text = if capture.action == Some(action) && capture.slot == slot {
+                        "...".to_string()
+                    } else {
+                        slot.map_or_else(|| "+".to_string(), |input| input.label())
+                    };
+                    children
+                        .button(text)
+                        .insert(ControlsAction::Rebind(action, slot));
+                }
+            });
+    }
+}
@@ -27,7 +27,9 @@ fn enter_credits(mut commands: Commands) {
         .with_children(|children| {
             children.header("CREDITS");
             children.label("Didn't have time to put in game, sorry.\n Check the game's github page. I'll put it there a couple days after release.");
-            children.button("BACK").insert(CreditsAction::Back);
+            children
+                .button("BACK")
+                .insert((CreditsAction::Back, BackButton));
         });
 }
 
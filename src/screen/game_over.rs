@@ -1,43 +1,178 @@
-//! The title screen that appears when the game starts.
+//! The game over screen with a summary of the finished run.
 
 use bevy::prelude::*;
 
 use super::{NextTransitionedState, Screen};
-use crate::{game::score::Score, ui::prelude::*};
+use crate::{
+    game::{
+        audio::soundtrack::{MusicTrack, PlayMusic},
+        ball::{ball_speed_tier, BALL_SPEED_TIERS},
+        leaderboard::{format_duration, record_run, LastRunRank},
+        replay::RecordedReplay,
+        rng::{GameRng, RunSeed},
+        score::{Score, ScoreBreakdown},
+        spawn::enemy::EnemyKind,
+        stats::RunStats,
+    },
+    ui::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::GameOver), enter_game_over)
+    app.add_systems(OnEnter(Screen::GameOver), enter_game_over.after(record_run))
         .add_systems(
             Update,
-            handle_title_action.run_if(in_state(Screen::GameOver)),
+            handle_game_over_action.run_if(in_state(Screen::GameOver)),
         );
+    app.register_type::<GameOverAction>();
 }
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[reflect(Component)]
-enum BtnAction {
-    Play,
+enum GameOverAction {
+    Retry,
+    RetrySameSeed,
+    MainMenu,
+    SaveReplay,
 }
 
-fn enter_game_over(mut commands: Commands, score: Res<Score>) {
+#[derive(Component)]
+struct ReplayStatus;
+
+fn enter_game_over(
+    mut commands: Commands,
+    score: Res<Score>,
+    breakdown: Res<ScoreBreakdown>,
+    rank: Res<LastRunRank>,
+    stats: Res<RunStats>,
+    rng: Res<GameRng>,
+    recorded: Res<RecordedReplay>,
+) {
+    commands.trigger(PlayMusic::Track(MusicTrack::GameOver));
+
+    let kills = EnemyKind::ALL
+        .iter()
+        .map(|kind| {
+            format!(
+                "{} {}",
+                kind.name().replace('_', " ").to_uppercase(),
+                stats.kills_of(*kind)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("   ");
+    let accuracy = if stats.ammo_used > 0 {
+        stats.shots_hit * 100 / stats.ammo_used
+    } else {
+        0
+    };
+
     commands
         .ui_root()
         .insert(StateScoped(Screen::GameOver))
         .with_children(|children| {
             children.label("GAME OVER");
+            if rank.is_new_best() {
+                children.header("NEW BEST!");
+            }
             children.label(format!("SCORE: {}", score.0));
-            children.button("TRY AGAIN").insert(BtnAction::Play);
+            children.label(format!(
+                "KILLS {} + COMBO {} + BALL SPEED {} + MULTI-KILL {} + ON BEAT {}",
+                breakdown.base,
+                breakdown.combo_bonus,
+                breakdown.ball_speed_bonus,
+                breakdown.multi_kill_bonus,
+                breakdown.rhythm_bonus
+            ));
+            if let Some(rank) = rank.0.filter(|r| *r > 0) {
+                children.label(format!("#{} ON THE LEADERBOARD", rank + 1));
+            }
+
+            children.label(format!(
+                "SURVIVED {}   DAMAGE TAKEN {}",
+                format_duration(stats.duration),
+                stats.core_damage.len()
+            ));
+            children.label(format!("{} KILLS: {kills}", stats.total_kills()));
+            children.label(format!(
+                "BEST COMBO x{}   BEST MULTI-KILL {}",
+                breakdown.best_combo_multiplier, breakdown.best_multi_kill
+            ));
+            children.label(format!(
+                "TOP BALL SPEED {:.0} ({}/{BALL_SPEED_TIERS})",
+                stats.max_ball_speed,
+                ball_speed_tier(stats.max_ball_speed)
+            ));
+            children.label(format!(
+                "SHOTS HIT {}/{} ({accuracy}%)",
+                stats.shots_hit, stats.ammo_used
+            ));
+            children.label(format!("SEED {}", rng.seed()));
+
+            children
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_wrap: FlexWrap::Wrap,
+                        justify_content: JustifyContent::Center,
+                        column_gap: Val::Px(12.),
+                        row_gap: Val::Px(12.),
+                        margin: UiRect::top(Val::Px(12.)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|buttons| {
+                    buttons.button("RETRY").insert(GameOverAction::Retry);
+                    buttons
+                        .button("RETRY SAME SEED")
+                        .insert(GameOverAction::RetrySameSeed);
+                    buttons
+                        .button("MAIN MENU")
+                        .insert((GameOverAction::MainMenu, BackButton));
+                    if recorded.replay().is_some() {
+                        buttons
+                            .button("SAVE REPLAY")
+                            .insert(GameOverAction::SaveReplay);
+                    }
+                });
+            children.label("").insert(ReplayStatus);
         });
 }
 
-fn handle_title_action(
+fn handle_game_over_action(
     mut next_screen: ResMut<NextTransitionedState>,
-    mut button_query: InteractionQuery<&BtnAction>,
+    mut button_query: InteractionQuery<&GameOverAction>,
+    status_q: Query<&Children, With<ReplayStatus>>,
+    mut text_q: Query<&mut Text>,
+    mut seed: ResMut<RunSeed>,
+    rng: Res<GameRng>,
+    recorded: Res<RecordedReplay>,
 ) {
     for (interaction, action) in &mut button_query {
         if matches!(interaction, Interaction::Pressed) {
             match action {
-                BtnAction::Play => next_screen.set(Screen::Game),
+                GameOverAction::Retry => next_screen.set(Screen::Game),
+                GameOverAction::RetrySameSeed => {
+                    seed.next = Some(rng.seed());
+                    next_screen.set(Screen::Game);
+                }
+                GameOverAction::MainMenu => next_screen.set(Screen::Title),
+                GameOverAction::SaveReplay => {
+                    let Some(replay) = recorded.replay() else {
+                        continue;
+                    };
+                    let status = match replay.save() {
+                        Ok(location) => format!("REPLAY SAVED TO {location}"),
+                        Err(err) => {
+                            error!("failed to save the replay: {err}");
+                            "FAILED TO SAVE THE REPLAY".to_string()
+                        }
+                    };
+                    // the label text is a child of the label node
+                    let mut texts = text_q.iter_many_mut(status_q.iter().flatten());
+                    while let Some(mut text) = texts.fetch_next() {
+                        text.sections[0].value = status.clone();
+                    }
+                }
             }
         }
     }
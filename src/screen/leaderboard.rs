@@ -0,0 +1,159 @@
+//! A leaderboard screen listing the best local and, if available, online runs.
+
+use bevy::prelude::*;
+
+use super::{NextTransitionedState, Screen};
+use crate::{
+    game::{
+        leaderboard::{format_duration, Leaderboard},
+        online::{OnlineLeaderboard, OnlineTop},
+    },
+    ui::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LeaderboardView>();
+    app.add_systems(OnEnter(Screen::Leaderboard), enter_leaderboard);
+    app.add_systems(
+        Update,
+        (
+            handle_leaderboard_action,
+            update_score_list.run_if(
+                resource_changed::<LeaderboardView>
+                    .or_else(resource_exists_and_changed::<OnlineLeaderboard>),
+            ),
+        )
+            .chain()
+            .run_if(in_state(Screen::Leaderboard)),
+    );
+    app.register_type::<LeaderboardAction>();
+}
+
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+enum LeaderboardView {
+    #[default]
+    Local,
+    Online,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum LeaderboardAction {
+    Show(LeaderboardView),
+    Back,
+}
+
+#[derive(Component)]
+struct ScoreList;
+
+fn enter_leaderboard(
+    mut commands: Commands,
+    mut view: ResMut<LeaderboardView>,
+    online: Option<Res<OnlineLeaderboard>>,
+) {
+    // triggers the list update
+    *view = LeaderboardView::Local;
+
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Leaderboard))
+        .with_children(|children| {
+            children.header("LEADERBOARD");
+
+            if online.is_some() {
+                children
+                    .button("LOCAL")
+                    .insert(LeaderboardAction::Show(LeaderboardView::Local));
+                children
+                    .button("ONLINE")
+                    .insert(LeaderboardAction::Show(LeaderboardView::Online));
+            }
+
+            children.spawn((
+                Name::new("Score list"),
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(8.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ScoreList,
+            ));
+
+            children
+                .button("BACK")
+                .insert((LeaderboardAction::Back, BackButton));
+        });
+}
+
+fn update_score_list(
+    mut cmd: Commands,
+    list_q: Query<Entity, With<ScoreList>>,
+    view: Res<LeaderboardView>,
+    leaderboard: Res<Leaderboard>,
+    online: Option<Res<OnlineLeaderboard>>,
+) {
+    let Ok(list_e) = list_q.get_single() else {
+        return;
+    };
+
+    cmd.entity(list_e)
+        .despawn_descendants()
+        .with_children(|children| match (*view, online.as_ref().map(|o| o.top())) {
+            (LeaderboardView::Online, Some(OnlineTop::Loaded(scores))) => {
+                if scores.is_empty() {
+                    children.label("No online runs yet.");
+                }
+                for (i, score) in scores.iter().enumerate() {
+                    children.label(format!(
+                        "#{}   {}   {}   seed {}",
+                        i + 1,
+                        score.score,
+                        format_duration(score.duration),
+                        score.seed
+                    ));
+                }
+            }
+            (LeaderboardView::Online, Some(OnlineTop::Failed(_))) => {
+                children.label("Couldn't reach the online leaderboard.");
+            }
+            (LeaderboardView::Online, _) => {
+                children.label("Loading...");
+            }
+            (LeaderboardView::Local, _) => {
+                if leaderboard.entries().is_empty() {
+                    children.label("No runs yet, go get some kills!");
+                }
+                for (i, entry) in leaderboard.entries().iter().enumerate() {
+                    children.label(format!(
+                        "#{}   {}   {}   {}   {}   seed {}",
+                        i + 1,
+                        entry.score,
+                        format_duration(entry.duration),
+                        entry.mode.name(),
+                        entry.date_label(),
+                        entry.seed
+                    ));
+                }
+            }
+        });
+}
+
+fn handle_leaderboard_action(
+    mut next_screen: ResMut<NextTransitionedState>,
+    mut view: ResMut<LeaderboardView>,
+    mut button_query: InteractionQuery<&LeaderboardAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                LeaderboardAction::Show(show) => *view = *show,
+                LeaderboardAction::Back => next_screen.set(Screen::Title),
+            }
+        }
+    }
+}
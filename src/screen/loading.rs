@@ -5,9 +5,10 @@ use bevy::prelude::*;
 
 use super::{NextTransitionedState, Screen};
 use crate::{
+    cli::LaunchOptions,
     game::{
-        assets::{MusicAssets, ParticleAssets},
-        audio::soundtrack::PlayMusic,
+        assets::ParticleAssets,
+        audio::soundtrack::{build_music_bank, MusicTrack, PlayMusic},
     },
     ui::prelude::*,
 };
@@ -15,7 +16,8 @@ use crate::{
 // todo: use transition
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Loading), enter_loading)
-        .add_systems(OnEnter(Screen::Loaded), on_loaded);
+        // the title music needs the bank
+        .add_systems(OnEnter(Screen::Loaded), on_loaded.after(build_music_bank));
 }
 
 fn enter_loading(mut commands: Commands) {
@@ -31,10 +33,10 @@ fn on_loaded(
     mut next_screen: ResMut<NextTransitionedState>,
     mut cmd: Commands,
     particles: Res<ParticleAssets>,
-    music: Res<MusicAssets>,
+    options: Res<LaunchOptions>,
 ) {
-    next_screen.set(Screen::Title);
+    next_screen.set(options.screen.clone().unwrap_or(Screen::Title));
     // bg particles
     cmd.spawn((particles.particle_spawner(particles.bg.clone(), Transform::default()),));
-    cmd.trigger(PlayMusic::Track(music.track_1.clone()));
+    cmd.trigger(PlayMusic::Track(MusicTrack::Title));
 }
@@ -1,9 +1,13 @@
 //! The game's main screen states and transitions between them.
 
+mod controls;
 mod credits;
 mod game_over;
+mod leaderboard;
 mod loading;
+pub mod pause;
 mod playing;
+mod settings;
 mod splash;
 mod title;
 mod tutorial;
@@ -15,7 +19,10 @@ use crate::{
         assets::{assets_exist, SpriteAssets},
         tween::{tween_factor, TweenFactor},
     },
-    ui::palette::{COL_BG, COL_LETTERBOX, COL_TRANSITION_1, COL_TRANSITION_2, COL_TRANSITION_3},
+    settings::Settings,
+    ui::palette::{
+        Tint, COL_BG, COL_LETTERBOX, COL_TRANSITION_1, COL_TRANSITION_2, COL_TRANSITION_3,
+    },
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -30,6 +37,10 @@ pub(super) fn plugin(app: &mut App) {
             playing::plugin,
             game_over::plugin,
             tutorial::plugin,
+            leaderboard::plugin,
+            pause::plugin,
+            settings::plugin,
+            controls::plugin,
         ))
         .add_systems(OnExit(Screen::Loading), setup_transition_overlay)
         .add_systems(Startup, setup_letterbox)
@@ -60,6 +71,9 @@ pub enum Screen {
     Title,
     Credits,
     Tutorial,
+    Leaderboard,
+    Settings,
+    Controls,
     Game,
     RestartGame,
     GameOver,
@@ -111,7 +125,8 @@ fn setup_transition_overlay(mut cmd: Commands, sprites: ResMut<SpriteAssets>) {
                 },
             ));
             if i == colors.len() - 1 {
-                builder.insert(FinalTransitionCircle);
+                // fades into the game world's background
+                builder.insert((FinalTransitionCircle, Tint::Bg));
             }
             builder.id()
         })
@@ -253,11 +268,25 @@ fn start_transition_anim(
         )>,
     >,
     next_transitioned: Res<NextTransitionedState>,
+    final_circle_q: Query<Entity, With<FinalTransitionCircle>>,
+    settings: Res<Settings>,
 ) {
     if !circle_q.is_empty() || next_transitioned.0.is_none() {
         return;
     }
 
+    // a fade of just the final circle
+    if settings.accessibility.reduced_motion {
+        for e in &final_circle_q {
+            cmd.entity(e)
+                .try_insert(TweenFactor::<TransitionCircle>::new(
+                    400,
+                    bevy_tweening::EaseFunction::QuadraticOut,
+                ));
+        }
+        return;
+    }
+
     for (i, e) in trans.circle_entity_ids.iter().cloned().enumerate() {
         cmd.entity(e).try_insert(
             TweenFactor::<TransitionCircle>::new(800, bevy_tweening::EaseFunction::SineInOut)
@@ -276,18 +305,26 @@ fn transition_out(
         Changed<TweenFactor<TransitionCircle>>,
     >,
     mut style_q: Query<&mut Style>,
+    mut image_q: Query<&mut UiImage>,
     reset_circle_q: Query<Entity, (With<TransitionCircle>, Without<FinalTransitionCircle>)>,
     mut cmd: Commands,
     next_transitioned: Res<NextTransitionedState>,
     mut next_state: ResMut<NextState<Screen>>,
+    settings: Res<Settings>,
 ) {
     for (e, factor, final_circle) in &mut circle_q {
         let factor = factor.factor();
+        let reduced_motion = settings.accessibility.reduced_motion;
         if let Ok(mut style) = style_q.get_mut(e) {
-            let size = Val::VMax(145.0 * factor);
+            let size = Val::VMax(145.0 * if reduced_motion { 1. } else { factor });
             style.width = size;
             style.height = size;
         }
+        if reduced_motion {
+            if let Ok(mut image) = image_q.get_mut(e) {
+                image.color.set_alpha(factor);
+            }
+        }
 
         if factor >= 1. {
             if let Some(new_state) = &next_transitioned.0 {
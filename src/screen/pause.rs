@@ -0,0 +1,178 @@
+//! The pause menu over the running game.
+
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowFocused},
+};
+use leafwing_input_manager::common_conditions::action_just_pressed;
+
+use super::{
+    controls::spawn_controls_menu, playing, settings::spawn_settings_menu, NextTransitionedState,
+    Screen, TransitionCircle,
+};
+use crate::{
+    game::{input::PlayerAction, tween::TweenFactor},
+    settings::Settings,
+    ui::{palette::COL_BG, prelude::*},
+    AppSet,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_sub_state::<Pause>()
+        .add_sub_state::<PauseMenu>()
+        .enable_state_scoped_entities::<PauseMenu>()
+        // no player input gets through while paused
+        .configure_sets(
+            Update,
+            AppSet::ProcessInput.run_if(not(in_state(Pause::Paused))),
+        )
+        .add_systems(OnEnter(Pause::Paused), (pause_time, release_cursor))
+        .add_systems(OnEnter(PauseMenu::Main), spawn_pause_menu)
+        .add_systems(OnEnter(PauseMenu::Settings), spawn_pause_settings)
+        .add_systems(OnEnter(PauseMenu::Controls), spawn_pause_controls)
+        .add_systems(OnExit(Pause::Paused), (unpause_time, grab_cursor))
+        .add_systems(
+            Update,
+            (
+                // the other pages go back with the back action instead
+                toggle_pause.run_if(
+                    action_just_pressed(PlayerAction::Quit)
+                        .and_then(not(in_state(Pause::Paused)).or_else(in_state(PauseMenu::Main))),
+                ),
+                pause_on_focus_lost,
+                handle_pause_action.run_if(in_state(PauseMenu::Main)),
+            )
+                .run_if(in_state(Screen::Game)),
+        );
+    app.register_type::<PauseAction>();
+}
+
+#[derive(SubStates, Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[source(Screen = Screen::Game)]
+pub enum Pause {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// Page of the pause menu.
+#[derive(SubStates, Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+#[source(Pause = Pause::Paused)]
+pub enum PauseMenu {
+    #[default]
+    Main,
+    Settings,
+    Controls,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum PauseAction {
+    Resume,
+    Restart,
+    Settings,
+    QuitToMenu,
+}
+
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn unpause_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn release_cursor(mut window_q: Query<&mut Window, With<PrimaryWindow>>) {
+    if let Ok(mut win) = window_q.get_single_mut() {
+        playing::release_cursor(&mut win);
+    }
+}
+
+fn grab_cursor(mut window_q: Query<&mut Window, With<PrimaryWindow>>, settings: Res<Settings>) {
+    if let Ok(mut win) = window_q.get_single_mut() {
+        playing::grab_cursor(&mut win, &settings);
+    }
+}
+
+fn spawn_pause_menu(mut cmd: Commands) {
+    cmd.ui_root()
+        .insert((
+            Name::new("Pause menu"),
+            StateScoped(PauseMenu::Main),
+            BackgroundColor(COL_BG.with_alpha(0.8)),
+        ))
+        .with_children(|children| {
+            children.header("PAUSED");
+            children
+                .button("RESUME")
+                .insert((PauseAction::Resume, BackButton));
+            children.button("RESTART").insert(PauseAction::Restart);
+            children.button("SETTINGS").insert(PauseAction::Settings);
+            children
+                .button("QUIT TO MENU")
+                .insert(PauseAction::QuitToMenu);
+        });
+}
+
+fn spawn_pause_settings(mut cmd: Commands, settings: Res<Settings>) {
+    cmd.ui_root()
+        .insert((
+            Name::new("Pause settings"),
+            StateScoped(PauseMenu::Settings),
+            BackgroundColor(COL_BG.with_alpha(0.8)),
+        ))
+        .with_children(|children| spawn_settings_menu(children, &settings));
+}
+
+fn spawn_pause_controls(mut cmd: Commands) {
+    cmd.ui_root()
+        .insert((
+            Name::new("Pause controls"),
+            StateScoped(PauseMenu::Controls),
+            BackgroundColor(COL_BG.with_alpha(0.8)),
+        ))
+        .with_children(spawn_controls_menu);
+}
+
+fn toggle_pause(
+    pause: Res<State<Pause>>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    transition_q: Query<(), With<TweenFactor<TransitionCircle>>>,
+) {
+    if !transition_q.is_empty() {
+        // already leaving the game
+        return;
+    }
+
+    next_pause.set(match pause.get() {
+        Pause::Running => Pause::Paused,
+        Pause::Paused => Pause::Running,
+    });
+}
+
+fn pause_on_focus_lost(
+    mut focus_r: EventReader<WindowFocused>,
+    mut next_pause: ResMut<NextState<Pause>>,
+) {
+    if focus_r.read().any(|ev| !ev.focused) {
+        next_pause.set(Pause::Paused);
+    }
+}
+
+fn handle_pause_action(
+    mut next_screen: ResMut<NextTransitionedState>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut next_pause_menu: ResMut<NextState<PauseMenu>>,
+    mut button_query: InteractionQuery<&PauseAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                PauseAction::Resume => next_pause.set(Pause::Running),
+                PauseAction::Restart => next_screen.set(Screen::RestartGame),
+                PauseAction::Settings => next_pause_menu.set(PauseMenu::Settings),
+                PauseAction::QuitToMenu => next_screen.set(Screen::Title),
+            }
+        }
+    }
+}
@@ -7,12 +7,14 @@ use bevy::{
 use leafwing_input_manager::common_conditions::action_just_pressed;
 
 use super::{NextTransitionedState, Screen};
-use crate::game::{
-    // assets::SoundtrackKey,
-    audio::soundtrack::PlayMusic,
-    input::PlayerAction,
-    score::Score,
-    spawn::level::SpawnLevel,
+use crate::{
+    game::{
+        audio::soundtrack::{MusicTrack, PlayMusic},
+        input::PlayerAction,
+        spawn::level::SpawnLevel,
+    },
+    settings::Settings,
+    AppSet,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -21,13 +23,9 @@ pub(super) fn plugin(app: &mut App) {
         .add_systems(OnEnter(Screen::RestartGame), enter_restart)
         .add_systems(
             Update,
-            (
-                return_to_title_screen.run_if(
-                    in_state(Screen::Game).and_then(action_just_pressed(PlayerAction::Quit)),
-                ),
-                restart_game.run_if(
-                    in_state(Screen::Game).and_then(action_just_pressed(PlayerAction::Restart)),
-                ),
+            // player input, so blocked while paused
+            restart_game.in_set(AppSet::ProcessInput).run_if(
+                in_state(Screen::Game).and_then(action_just_pressed(PlayerAction::Restart)),
             ),
         );
 }
@@ -35,33 +33,41 @@ pub(super) fn plugin(app: &mut App) {
 fn enter_playing(
     mut cmd: Commands,
     mut window_q: Query<&mut Window, With<PrimaryWindow>>,
-    mut score: ResMut<Score>,
+    settings: Res<Settings>,
 ) {
     cmd.trigger(SpawnLevel);
-    // commands.trigger(PlaySoundtrack::Key(SoundtrackKey::Gameplay));
-    let mut win = window_q.single_mut();
-    // reset score
-    score.0 = 0;
+    cmd.trigger(PlayMusic::Restart(MusicTrack::Gameplay));
 
-    #[cfg(not(target_family = "wasm"))]
-    {
-        win.cursor.grab_mode = CursorGrabMode::Confined;
+    if let Ok(mut win) = window_q.get_single_mut() {
+        grab_cursor(&mut win, &settings);
     }
 }
 
-fn exit_playing(mut commands: Commands, mut window_q: Query<&mut Window, With<PrimaryWindow>>) {
-    // We could use [`StateScoped`] on the sound playing entites instead.
-    commands.trigger(PlayMusic::Disable);
-    let mut win = window_q.single_mut();
+/// The next screen picks its music.
+fn exit_playing(mut window_q: Query<&mut Window, With<PrimaryWindow>>) {
+    if let Ok(mut win) = window_q.get_single_mut() {
+        release_cursor(&mut win);
+    }
+}
 
-    #[cfg(not(target_family = "wasm"))]
-    {
-        win.cursor.grab_mode = CursorGrabMode::None;
+/// Confines the cursor to the window, or locks & hides it for the relative mouse aim.
+pub(super) fn grab_cursor(win: &mut Window, settings: &Settings) {
+    if settings.relative_mouse_aim {
+        win.cursor.grab_mode = CursorGrabMode::Locked;
+        win.cursor.visible = false;
+    } else {
+        // confining isn't supported on the web
+        #[cfg(not(target_family = "wasm"))]
+        {
+            win.cursor.grab_mode = CursorGrabMode::Confined;
+        }
+        win.cursor.visible = true;
     }
 }
 
-fn return_to_title_screen(mut next_screen: ResMut<NextTransitionedState>) {
-    next_screen.set(Screen::Title);
+pub(super) fn release_cursor(win: &mut Window) {
+    win.cursor.grab_mode = CursorGrabMode::None;
+    win.cursor.visible = true;
 }
 
 fn restart_game(mut next_screen: ResMut<NextTransitionedState>) {
@@ -0,0 +1,311 @@
+//! The settings menu, reachable from the title screen and the pause menu.
+
+use bevy::prelude::*;
+
+use super::{pause::PauseMenu, NextTransitionedState, Screen};
+use crate::{settings::Settings, ui::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Settings), enter_settings)
+        .add_systems(
+            Update,
+            (
+                apply_sliders,
+                apply_toggles,
+                handle_settings_action,
+                update_palette_button.run_if(resource_changed::<Settings>),
+            ),
+        )
+        .register_type::<SettingsAction>();
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingSlider {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    UiVolume,
+    BloomIntensity,
+    ShakeStrength,
+    AimDeadzone,
+    MouseSensitivity,
+    AimAssist,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingToggle {
+    Fullscreen,
+    Vsync,
+    RelativeMouseAim,
+    MuteMaster,
+    MuteMusic,
+    MuteSfx,
+    MuteUi,
+    DistanceAttenuation,
+    ReducedMotion,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum SettingsAction {
+    /// Switches to the next built-in palette.
+    CyclePalette,
+    Controls,
+    Back,
+}
+
+fn enter_settings(mut commands: Commands, settings: Res<Settings>) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Settings))
+        .with_children(|children| spawn_settings_menu(children, &settings));
+}
+
+/// Spawns the settings widgets, also used by the pause menu.
+pub(super) fn spawn_settings_menu(children: &mut ChildBuilder, settings: &Settings) {
+    children.header("SETTINGS");
+
+    let column = || NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(12.),
+            ..default()
+        },
+        ..default()
+    };
+    children
+        .spawn(NodeBundle {
+            style: Style {
+                flex_wrap: FlexWrap::Wrap,
+                justify_content: JustifyContent::Center,
+                column_gap: Val::Px(24.),
+                row_gap: Val::Px(12.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|children| {
+            children
+                .spawn(column())
+                .with_children(|children| spawn_audio_video_settings(children, settings));
+            children
+                .spawn(column())
+                .with_children(|children| spawn_gameplay_settings(children, settings));
+        });
+
+    children
+        .spawn(NodeBundle {
+            style: Style {
+                column_gap: Val::Px(12.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|children| {
+            children.button("CONTROLS").insert(SettingsAction::Controls);
+            children
+                .button("BACK")
+                .insert((SettingsAction::Back, BackButton));
+        });
+}
+
+fn spawn_audio_video_settings(children: &mut ChildBuilder, settings: &Settings) {
+    let buses = [
+        (
+            "MASTER VOLUME",
+            settings.master_volume,
+            SettingSlider::MasterVolume,
+            settings.muted.master,
+            SettingToggle::MuteMaster,
+        ),
+        (
+            "MUSIC VOLUME",
+            settings.music_volume,
+            SettingSlider::MusicVolume,
+            settings.muted.music,
+            SettingToggle::MuteMusic,
+        ),
+        (
+            "SFX VOLUME",
+            settings.sfx_volume,
+            SettingSlider::SfxVolume,
+            settings.muted.sfx,
+            SettingToggle::MuteSfx,
+        ),
+        (
+            "UI VOLUME",
+            settings.ui_volume,
+            SettingSlider::UiVolume,
+            settings.muted.ui,
+            SettingToggle::MuteUi,
+        ),
+    ];
+    for (caption, volume, slider, muted, toggle) in buses {
+        children
+            .spawn(NodeBundle {
+                style: Style {
+                    column_gap: Val::Px(12.),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|children| {
+                children
+                    .slider(
+                        Slider::new(caption, volume, 0., 1.)
+                            .with_step(0.05)
+                            .with_format(SliderFormat::Percent),
+                    )
+                    .insert(slider);
+                children.toggle("MUTE", muted).insert(toggle);
+            });
+    }
+    children
+        .toggle("DISTANCE FALLOFF", settings.distance_attenuation)
+        .insert(SettingToggle::DistanceAttenuation);
+
+    children
+        .toggle("FULLSCREEN", settings.fullscreen)
+        .insert(SettingToggle::Fullscreen);
+    children
+        .toggle("VSYNC", settings.vsync)
+        .insert(SettingToggle::Vsync);
+
+    children
+        .slider(
+            Slider::new("BLOOM", settings.bloom_intensity, 0., 2.)
+                .with_step(0.1)
+                .with_format(SliderFormat::Percent),
+        )
+        .insert(SettingSlider::BloomIntensity);
+    children
+        .toggle("REDUCED MOTION", settings.accessibility.reduced_motion)
+        .insert(SettingToggle::ReducedMotion);
+    children
+        .button(palette_text(settings))
+        .insert(SettingsAction::CyclePalette);
+}
+
+fn palette_text(settings: &Settings) -> String {
+    format!("PALETTE: {}", settings.accessibility.palette.name())
+}
+
+fn spawn_gameplay_settings(children: &mut ChildBuilder, settings: &Settings) {
+    children
+        .slider(
+            Slider::new("SCREEN SHAKE", settings.shake_strength, 0., 2.)
+                .with_step(0.1)
+                .with_format(SliderFormat::Percent),
+        )
+        .insert(SettingSlider::ShakeStrength);
+    children
+        .slider(Slider::new("AIM DEADZONE", settings.aim_deadzone, 0., 200.).with_step(5.))
+        .insert(SettingSlider::AimDeadzone);
+    children
+        .toggle("RELATIVE MOUSE AIM", settings.relative_mouse_aim)
+        .insert(SettingToggle::RelativeMouseAim);
+    children
+        .slider(
+            Slider::new("MOUSE SENSITIVITY", settings.mouse_sensitivity, 0.1, 3.)
+                .with_step(0.1)
+                .with_format(SliderFormat::Percent),
+        )
+        .insert(SettingSlider::MouseSensitivity);
+    children
+        .slider(
+            Slider::new("PAD AIM ASSIST", settings.aim_assist, 0., 1.)
+                .with_step(0.05)
+                .with_format(SliderFormat::Percent),
+        )
+        .insert(SettingSlider::AimAssist);
+}
+
+fn apply_sliders(
+    slider_q: Query<(&Slider, &SettingSlider), Changed<Slider>>,
+    mut settings: ResMut<Settings>,
+) {
+    let mut new_settings = settings.clone();
+    for (slider, setting) in &slider_q {
+        let value = match setting {
+            SettingSlider::MasterVolume => &mut new_settings.master_volume,
+            SettingSlider::MusicVolume => &mut new_settings.music_volume,
+            SettingSlider::SfxVolume => &mut new_settings.sfx_volume,
+            SettingSlider::UiVolume => &mut new_settings.ui_volume,
+            SettingSlider::BloomIntensity => &mut new_settings.bloom_intensity,
+            SettingSlider::ShakeStrength => &mut new_settings.shake_strength,
+            SettingSlider::AimDeadzone => &mut new_settings.aim_deadzone,
+            SettingSlider::MouseSensitivity => &mut new_settings.mouse_sensitivity,
+            SettingSlider::AimAssist => &mut new_settings.aim_assist,
+        };
+        *value = slider.value;
+    }
+    settings.set_if_neq(new_settings);
+}
+
+fn apply_toggles(
+    toggle_q: Query<(&Toggle, &SettingToggle), Changed<Toggle>>,
+    mut settings: ResMut<Settings>,
+) {
+    let mut new_settings = settings.clone();
+    for (toggle, setting) in &toggle_q {
+        let value = match setting {
+            SettingToggle::Fullscreen => &mut new_settings.fullscreen,
+            SettingToggle::Vsync => &mut new_settings.vsync,
+            SettingToggle::RelativeMouseAim => &mut new_settings.relative_mouse_aim,
+            SettingToggle::MuteMaster => &mut new_settings.muted.master,
+            SettingToggle::MuteMusic => &mut new_settings.muted.music,
+            SettingToggle::MuteSfx => &mut new_settings.muted.sfx,
+            SettingToggle::MuteUi => &mut new_settings.muted.ui,
+            SettingToggle::DistanceAttenuation => &mut new_settings.distance_attenuation,
+            SettingToggle::ReducedMotion => &mut new_settings.accessibility.reduced_motion,
+        };
+        *value = toggle.on;
+    }
+    settings.set_if_neq(new_settings);
+}
+
+fn handle_settings_action(
+    mut next_screen: ResMut<NextTransitionedState>,
+    mut next_pause_menu: Option<ResMut<NextState<PauseMenu>>>,
+    screen: Res<State<Screen>>,
+    mut settings: ResMut<Settings>,
+    mut button_query: InteractionQuery<&SettingsAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                SettingsAction::CyclePalette => {
+                    let palette = &mut settings.accessibility.palette;
+                    *palette = palette.next();
+                }
+                SettingsAction::Controls => match (screen.get(), next_pause_menu.as_mut()) {
+                    (Screen::Game, Some(next_pause_menu)) => {
+                        next_pause_menu.set(PauseMenu::Controls)
+                    }
+                    _ => next_screen.set(Screen::Controls),
+                },
+                SettingsAction::Back => match (screen.get(), next_pause_menu.as_mut()) {
+                    (Screen::Game, Some(next_pause_menu)) => next_pause_menu.set(PauseMenu::Main),
+                    _ => next_screen.set(Screen::Title),
+                },
+            }
+        }
+    }
+}
+
+fn update_palette_button(
+    settings: Res<Settings>,
+    button_q: Query<(&SettingsAction, &Children)>,
+    mut text_q: Query<&mut Text>,
+) {
+    for (action, children) in &button_q {
+        if *action != SettingsAction::CyclePalette {
+            continue;
+        }
+        let mut texts = text_q.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.sections[0].value = palette_text(&settings);
+        }
+    }
+}
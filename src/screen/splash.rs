@@ -8,7 +8,7 @@ use bevy::{
 use self::ui_palette::COL_BG;
 
 use super::Screen;
-use crate::{ui::prelude::*, AppSet};
+use crate::{cli::LaunchOptions, ui::prelude::*, AppSet};
 
 pub(super) fn plugin(app: &mut App) {
     app.insert_resource(ClearColor(COL_BG));
@@ -27,6 +27,7 @@ pub(super) fn plugin(app: &mut App) {
     // Add splash timer.
     app.register_type::<SplashTimer>();
     app.add_systems(OnEnter(Screen::Splash), insert_splash_timer);
+    app.add_systems(Startup, skip_splash);
     app.add_systems(OnExit(Screen::Splash), remove_splash_timer);
     app.add_systems(
         Update,
@@ -140,3 +141,9 @@ fn check_splash_timer(timer: ResMut<SplashTimer>, mut next_screen: ResMut<NextSt
         next_screen.set(Screen::Loading);
     }
 }
+
+fn skip_splash(options: Res<LaunchOptions>, mut next_screen: ResMut<NextState<Screen>>) {
+    if options.screen.is_some() {
+        next_screen.set(Screen::Loading);
+    }
+}
@@ -3,7 +3,14 @@
 use bevy::prelude::*;
 
 use super::{NextTransitionedState, Screen};
-use crate::ui::prelude::*;
+use crate::{
+    game::{
+        audio::soundtrack::{MusicTrack, PlayMusic},
+        rng::RunSeed,
+    },
+    settings::Settings,
+    ui::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Title), enter_title)
@@ -16,21 +23,33 @@ pub(super) fn plugin(app: &mut App) {
 #[reflect(Component)]
 enum TitleAction {
     Play,
+    /// Plays the seed of the last started run again.
+    PlayLastSeed,
     Credits,
     Tutorial,
+    Leaderboard,
+    Settings,
     /// Exit doesn't work well with embedded applications.
     #[cfg(not(target_family = "wasm"))]
     Exit,
 }
 
-fn enter_title(mut commands: Commands) {
+fn enter_title(mut commands: Commands, settings: Res<Settings>) {
+    commands.trigger(PlayMusic::Track(MusicTrack::Title));
     commands
         .ui_root()
         .insert(StateScoped(Screen::Title))
         .with_children(|children| {
             children.header("PORCLE");
             children.button("PLAY").insert(TitleAction::Play);
+            if let Some(seed) = settings.last_seed {
+                children
+                    .button(format!("LAST SEED {seed}"))
+                    .insert(TitleAction::PlayLastSeed);
+            }
             children.button("TUTORIAL").insert(TitleAction::Tutorial);
+            children.button("SCORES").insert(TitleAction::Leaderboard);
+            children.button("SETTINGS").insert(TitleAction::Settings);
             children.button("CREDITS").insert(TitleAction::Credits);
 
             #[cfg(not(target_family = "wasm"))]
@@ -41,12 +60,20 @@ fn enter_title(mut commands: Commands) {
 fn handle_title_action(
     mut next_screen: ResMut<NextTransitionedState>,
     mut button_query: InteractionQuery<&TitleAction>,
+    mut seed: ResMut<RunSeed>,
+    settings: Res<Settings>,
 ) {
     for (interaction, action) in &mut button_query {
         if matches!(interaction, Interaction::Pressed) {
             match action {
                 TitleAction::Play => next_screen.set(Screen::Game),
+                TitleAction::PlayLastSeed => {
+                    seed.next = settings.last_seed;
+                    next_screen.set(Screen::Game);
+                }
                 TitleAction::Tutorial => next_screen.set(Screen::Tutorial),
+                TitleAction::Leaderboard => next_screen.set(Screen::Leaderboard),
+                TitleAction::Settings => next_screen.set(Screen::Settings),
                 TitleAction::Credits => next_screen.set(Screen::Credits),
                 #[cfg(not(target_family = "wasm"))]
                 TitleAction::Exit => next_screen.set(Screen::Exit),
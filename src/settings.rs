@@ -0,0 +1,252 @@
+//! User settings, applied by the parts of the game they affect.
+//!
+//! They're stored as RON in the platform config directory (`localStorage` on the web),
+//! loaded at startup and saved shortly after every change.
+
+use bevy::{
+    prelude::*,
+    window::{PresentMode, PrimaryWindow, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cli::LaunchOptions,
+    game::input::{BoundInput, KeyBindings, PlayerAction},
+    storage,
+    ui::palette::PaletteKind,
+    Headless,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Settings>()
+        .add_systems(Startup, (load_settings, apply_launch_window).chain())
+        .add_systems(
+            Update,
+            (
+                apply_window_settings.run_if(resource_changed::<Settings>),
+                save_settings.run_if(not(resource_exists::<Headless>)),
+            ),
+        );
+}
+
+/// Bumped whenever the format changes in a way that needs a migration in [`parse_settings`].
+/// New fields don't need one, they get their default value.
+const SETTINGS_VERSION: u32 = 2;
+const SETTINGS_FILE: &str = "settings.ron";
+/// Settings get saved once they've stopped changing for this long, e.g. after dragging a slider.
+const SAVE_DELAY: f32 = 0.5;
+
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    version: u32,
+    /// The volumes scale the default mix.
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub ui_volume: f32,
+    pub muted: MutedBuses,
+    /// Quiets the gameplay sounds far from the core, they're panned either way.
+    pub distance_attenuation: bool,
+    pub fullscreen: bool,
+    pub vsync: bool,
+    /// Scales the bloom.
+    pub bloom_intensity: f32,
+    /// Scales the screen shake, 0 disables it.
+    pub shake_strength: f32,
+    /// Cursor distance from the center below which the mouse aim gets smoothed.
+    pub aim_deadzone: f32,
+    /// Turns the paddle by the mouse motion with a locked cursor instead of aiming at the cursor.
+    pub relative_mouse_aim: bool,
+    /// Scales the relative mouse aim.
+    pub mouse_sensitivity: f32,
+    /// How hard the gamepad aim gets pulled toward enemies, 0 disables it.
+    pub aim_assist: f32,
+    pub key_bindings: KeyBindings,
+    pub accessibility: Accessibility,
+    /// Seed of the last started run, offered on the title screen.
+    /// Written without change detection, see [`Settings::save`].
+    pub last_seed: Option<u64>,
+}
+
+/// Audio buses silenced regardless of their volume.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MutedBuses {
+    pub master: bool,
+    pub music: bool,
+    pub sfx: bool,
+    pub ui: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Accessibility {
+    /// No screen shake or beat pulses, capped bloom, outlines instead of flashes
+    /// & a fade instead of the circle transitions.
+    pub reduced_motion: bool,
+    /// Colours of the game world.
+    pub palette: PaletteKind,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            master_volume: 1.,
+            music_volume: 1.,
+            sfx_volume: 1.,
+            ui_volume: 1.,
+            muted: default(),
+            distance_attenuation: true,
+            fullscreen: false,
+            vsync: true,
+            bloom_intensity: 1.,
+            shake_strength: 1.,
+            aim_deadzone: 70.,
+            relative_mouse_aim: false,
+            mouse_sensitivity: 1.,
+            aim_assist: 0.5,
+            key_bindings: default(),
+            accessibility: default(),
+            last_seed: None,
+        }
+    }
+}
+
+impl Settings {
+    /// Writes the settings right away, for changes made without change detection.
+    pub fn save(&self) {
+        let settings = Settings {
+            version: SETTINGS_VERSION,
+            ..self.clone()
+        };
+        let res = ron::ser::to_string_pretty(&settings, default())
+            .map_err(|err| err.to_string())
+            .and_then(|content| storage::write_config(SETTINGS_FILE, &content));
+        if let Err(err) = res {
+            error!("failed to save the settings: {err}");
+        }
+    }
+}
+
+/// Just the version of the stored settings, to pick the migration.
+#[derive(Deserialize)]
+struct SettingsVersion {
+    #[serde(default)]
+    version: u32,
+}
+
+fn parse_settings(content: &str) -> Result<Settings, String> {
+    let stored: SettingsVersion = ron::from_str(content).map_err(|err| err.to_string())?;
+    match stored.version {
+        // older versions get migrated to the current one here
+        1 => {
+            let mut settings: Settings = ron::from_str(content).map_err(|err| err.to_string())?;
+            migrate_v1_bindings(&mut settings.key_bindings);
+            settings.version = SETTINGS_VERSION;
+            Ok(settings)
+        }
+        SETTINGS_VERSION => ron::from_str(content).map_err(|err| err.to_string()),
+        version => Err(format!("unsupported settings version {version}")),
+    }
+}
+
+/// Adds the keyboard-only scheme of v2: the rotations, and Space & Left Shift unless Shoot or
+/// the paddle mode were rebound. Inputs taken by other actions are skipped.
+fn migrate_v1_bindings(bindings: &mut KeyBindings) {
+    use BoundInput as B;
+    use PlayerAction as A;
+
+    let defaults = KeyBindings::default();
+    let extra_keys = [
+        (A::Shoot, B::Key(KeyCode::Space)),
+        (A::TogglePaddleMode, B::Key(KeyCode::ShiftLeft)),
+    ];
+    let unchanged = |action: PlayerAction| {
+        let v1_defaults: Vec<_> = defaults
+            .inputs_of(action)
+            .filter(|input| !extra_keys.contains(&(action, *input)))
+            .collect();
+        bindings.inputs_of(action).count() == v1_defaults.len()
+            && bindings
+                .inputs_of(action)
+                .all(|input| v1_defaults.contains(&input))
+    };
+    let added: Vec<_> = [A::RotateLeft, A::RotateRight]
+        .into_iter()
+        .flat_map(|action| defaults.inputs_of(action).map(move |input| (action, input)))
+        .chain(
+            extra_keys
+                .into_iter()
+                .filter(|(action, _)| unchanged(*action)),
+        )
+        .collect();
+
+    for (action, input) in added {
+        if bindings.action_of(input).is_none() {
+            bindings.0.push((action, input));
+        }
+    }
+}
+
+fn load_settings(mut settings: ResMut<Settings>) {
+    match storage::read_config(SETTINGS_FILE) {
+        Ok(Some(content)) => match parse_settings(&content) {
+            Ok(loaded) => *settings = loaded,
+            Err(err) => warn!("ignoring corrupt settings: {err}"),
+        },
+        Ok(None) => {}
+        Err(err) => error!("failed to load the settings: {err}"),
+    }
+}
+
+/// Launch options take precedence over the stored settings.
+fn apply_launch_window(options: Res<LaunchOptions>, mut settings: ResMut<Settings>) {
+    if let Some(mode) = options.window_mode {
+        settings.fullscreen = mode != WindowMode::Windowed;
+    }
+}
+
+fn save_settings(
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+    mut changed_at: Local<Option<f32>>,
+) {
+    // the loaded settings don't need to be saved
+    if settings.is_changed() && !settings.is_added() {
+        *changed_at = Some(time.elapsed_seconds());
+    }
+    if !changed_at.is_some_and(|at| time.elapsed_seconds() - at >= SAVE_DELAY) {
+        return;
+    }
+    *changed_at = None;
+    settings.save();
+}
+
+fn apply_window_settings(
+    settings: Res<Settings>,
+    mut window_q: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_q.get_single_mut() else {
+        return;
+    };
+
+    let mode = if settings.fullscreen {
+        WindowMode::BorderlessFullscreen
+    } else {
+        WindowMode::Windowed
+    };
+    if window.mode != mode {
+        window.mode = mode;
+    }
+
+    let present_mode = if settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
@@ -0,0 +1,107 @@
+//! Persistence of small text files.
+//! Native builds store them in the platform data (or config) directory, web builds in `localStorage`.
+
+#[derive(Debug, Clone, Copy)]
+enum Dir {
+    Data,
+    Config,
+}
+
+/// Reads the stored file, `None` if it hasn't been written yet.
+pub fn read(name: &str) -> Result<Option<String>, String> {
+    platform::read(Dir::Data, name)
+}
+
+pub fn write(name: &str, content: &str) -> Result<(), String> {
+    platform::write(Dir::Data, name, content)
+}
+
+/// Where the file is stored, for the player to find it.
+pub fn location(name: &str) -> String {
+    platform::location(Dir::Data, name)
+}
+
+/// Like [`read`], but for files in the config directory.
+pub fn read_config(name: &str) -> Result<Option<String>, String> {
+    platform::read(Dir::Config, name)
+}
+
+/// Like [`write`], but for files in the config directory.
+pub fn write_config(name: &str, content: &str) -> Result<(), String> {
+    platform::write(Dir::Config, name, content)
+}
+
+#[cfg(not(target_family = "wasm"))]
+mod platform {
+    use std::{fs, io, path::PathBuf};
+
+    use directories::ProjectDirs;
+
+    use super::Dir;
+
+    fn path(dir: Dir, name: &str) -> Result<PathBuf, String> {
+        let dirs = ProjectDirs::from("", "", "porcle").ok_or("no data directory available")?;
+        let dir = match dir {
+            Dir::Data => dirs.data_dir(),
+            Dir::Config => dirs.config_dir(),
+        };
+        Ok(dir.join(name))
+    }
+
+    pub(super) fn location(dir: Dir, name: &str) -> String {
+        path(dir, name).map_or_else(|_| name.to_string(), |path| path.display().to_string())
+    }
+
+    pub(super) fn read(dir: Dir, name: &str) -> Result<Option<String>, String> {
+        let path = path(dir, name)?;
+        match fs::read_to_string(&path) {
+            Ok(content) => Ok(Some(content)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(format!("failed to read {path:?}: {err}")),
+        }
+    }
+
+    pub(super) fn write(dir: Dir, name: &str, content: &str) -> Result<(), String> {
+        let path = path(dir, name)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| format!("failed to create {dir:?}: {err}"))?;
+        }
+        fs::write(&path, content).map_err(|err| format!("failed to write {path:?}: {err}"))
+    }
+}
+
+#[cfg(target_family = "wasm")]
+mod platform {
+    use web_sys::Storage;
+
+    use super::Dir;
+
+    fn storage() -> Result<Storage, String> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| "localStorage is not available".into())
+    }
+
+    fn key(dir: Dir, name: &str) -> String {
+        match dir {
+            Dir::Data => format!("porcle/{name}"),
+            Dir::Config => format!("porcle/config/{name}"),
+        }
+    }
+
+    pub(super) fn location(dir: Dir, name: &str) -> String {
+        format!("localStorage {}", key(dir, name))
+    }
+
+    pub(super) fn read(dir: Dir, name: &str) -> Result<Option<String>, String> {
+        storage()?
+            .get_item(&key(dir, name))
+            .map_err(|err| format!("failed to read {name}: {err:?}"))
+    }
+
+    pub(super) fn write(dir: Dir, name: &str, content: &str) -> Result<(), String> {
+        storage()?
+            .set_item(&key(dir, name), content)
+            .map_err(|err| format!("failed to write {name}: {err:?}"))
+    }
+}
@@ -1,8 +1,9 @@
 use bevy::prelude::*;
 
+use super::navigation::Focus;
 use crate::game::{
-    assets::{assets_exist, SfxAssets},
-    audio::sfx::PlaySfx,
+    assets::assets_exist,
+    audio::sfx::{PlaySfx, SfxKey},
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -26,10 +27,22 @@ pub struct InteractionPalette {
 }
 
 fn apply_interaction_palette(
-    mut palette_query: InteractionQuery<(&InteractionPalette, &mut BackgroundColor)>,
+    focus: Res<Focus>,
+    mut palette_query: Query<(
+        Entity,
+        Ref<Interaction>,
+        &InteractionPalette,
+        &mut BackgroundColor,
+    )>,
 ) {
-    for (interaction, (palette, mut background)) in &mut palette_query {
-        *background = match interaction {
+    for (entity, interaction, palette, mut background) in &mut palette_query {
+        if !interaction.is_changed() && !focus.is_changed() {
+            continue;
+        }
+
+        *background = match *interaction {
+            // the focused button looks hovered
+            Interaction::None if focus.0 == Some(entity) => palette.hovered,
             Interaction::None => palette.none,
             Interaction::Hovered => palette.hovered,
             Interaction::Pressed => palette.pressed,
@@ -41,12 +54,11 @@ fn apply_interaction_palette(
 fn trigger_interaction_sfx(
     mut interactions: Query<&Interaction, Changed<Interaction>>,
     mut commands: Commands,
-    sfx: Res<SfxAssets>,
 ) {
     for interaction in &mut interactions {
         match interaction {
-            Interaction::Hovered => commands.trigger(PlaySfx(sfx.button_hover.clone())),
-            Interaction::Pressed => commands.trigger(PlaySfx(sfx.button_click.clone())),
+            Interaction::Hovered => commands.trigger(PlaySfx::new(SfxKey::ButtonHover)),
+            Interaction::Pressed => commands.trigger(PlaySfx::new(SfxKey::ButtonClick)),
             _ => (),
         }
     }
@@ -4,19 +4,26 @@
 #![allow(dead_code, unused_imports)]
 
 pub mod interaction;
+pub mod navigation;
 pub mod palette;
 mod widgets;
 
 pub mod prelude {
     pub use super::{
         interaction::{InteractionPalette, InteractionQuery},
+        navigation::BackButton,
         palette as ui_palette,
-        widgets::{Containers as _, Widgets as _},
+        widgets::{Containers as _, Slider, SliderFormat, Toggle, Widgets as _},
     };
 }
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((
+        interaction::plugin,
+        navigation::plugin,
+        palette::plugin,
+        widgets::plugin,
+    ));
 }
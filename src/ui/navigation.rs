@@ -0,0 +1,224 @@
+//! Focus based menu navigation with the keyboard & gamepad.
+//!
+//! Confirming presses the focused button by setting its [`Interaction`] for a frame,
+//! so the menus handle it like a click.
+
+use bevy::{input::InputSystem, prelude::*, ui::UiSystem};
+
+use super::widgets::Slider;
+use crate::game::{
+    assets::assets_exist,
+    audio::sfx::{PlaySfx, SfxKey},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Focus>().add_systems(
+        PreUpdate,
+        (release_nav_press, clear_focus_on_cursor, navigate)
+            .chain()
+            .in_set(NavigationSystem)
+            .after(InputSystem)
+            .after(UiSystem::Focus)
+            .run_if(assets_exist),
+    );
+}
+
+/// Runs after the UI focus, before the menus handle their button presses.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NavigationSystem;
+
+/// The button focused by keyboard or gamepad navigation, drawn as hovered.
+#[derive(Resource, Debug, Default)]
+pub struct Focus(pub Option<Entity>);
+
+/// Marks the button pressed by the back action.
+#[derive(Component, Debug)]
+pub struct BackButton;
+
+/// Stick deflection that counts as a direction press.
+const STICK_THRESHOLD: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NavInput {
+    Move(Dir2),
+    Next,
+    Previous,
+    Confirm,
+    Back,
+}
+
+/// The button pressed by the last confirm, released on the next frame.
+#[derive(Resource, Debug)]
+struct NavPress(Entity);
+
+fn release_nav_press(
+    mut cmd: Commands,
+    press: Option<Res<NavPress>>,
+    mut interaction_q: Query<&mut Interaction>,
+) {
+    let Some(press) = press else {
+        return;
+    };
+    if let Ok(mut interaction) = interaction_q.get_mut(press.0) {
+        if *interaction == Interaction::Pressed {
+            *interaction = Interaction::None;
+        }
+    }
+    cmd.remove_resource::<NavPress>();
+}
+
+/// The mouse takes over from the focus.
+fn clear_focus_on_cursor(mut focus: ResMut<Focus>, mut cursor_evr: EventReader<CursorMoved>) {
+    if cursor_evr.read().count() > 0 && focus.0.is_some() {
+        focus.0 = None;
+    }
+}
+
+fn read_nav_input(
+    keys: &ButtonInput<KeyCode>,
+    gamepad_buttons: &ButtonInput<GamepadButton>,
+    gamepads: &Gamepads,
+    axes: &Axis<GamepadAxis>,
+    last_stick_dir: &mut Option<Dir2>,
+) -> Option<NavInput> {
+    let pressed = |key: KeyCode, button: GamepadButtonType| {
+        keys.just_pressed(key)
+            || gamepad_buttons
+                .get_just_pressed()
+                .any(|b| b.button_type == button)
+    };
+
+    // UI coordinates grow downwards
+    let stick_dir = gamepads.iter().find_map(|gamepad| {
+        let x = axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))?;
+        let y = axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))?;
+        if x.abs().max(y.abs()) < STICK_THRESHOLD {
+            None
+        } else if x.abs() > y.abs() {
+            Some(Dir2::new_unchecked(Vec2::X * x.signum()))
+        } else {
+            Some(Dir2::new_unchecked(Vec2::NEG_Y * y.signum()))
+        }
+    });
+    let stick_moved = stick_dir.filter(|dir| *last_stick_dir != Some(*dir));
+    *last_stick_dir = stick_dir;
+
+    if pressed(KeyCode::ArrowUp, GamepadButtonType::DPadUp) {
+        Some(NavInput::Move(Dir2::NEG_Y))
+    } else if pressed(KeyCode::ArrowDown, GamepadButtonType::DPadDown) {
+        Some(NavInput::Move(Dir2::Y))
+    } else if pressed(KeyCode::ArrowLeft, GamepadButtonType::DPadLeft) {
+        Some(NavInput::Move(Dir2::NEG_X))
+    } else if pressed(KeyCode::ArrowRight, GamepadButtonType::DPadRight) {
+        Some(NavInput::Move(Dir2::X))
+    } else if keys.just_pressed(KeyCode::Tab) {
+        if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            Some(NavInput::Previous)
+        } else {
+            Some(NavInput::Next)
+        }
+    } else if pressed(KeyCode::Enter, GamepadButtonType::South)
+        || keys.any_just_pressed([KeyCode::NumpadEnter, KeyCode::Space])
+    {
+        Some(NavInput::Confirm)
+    } else if pressed(KeyCode::Escape, GamepadButtonType::East)
+        || keys.just_pressed(KeyCode::Backspace)
+    {
+        Some(NavInput::Back)
+    } else {
+        stick_moved.map(NavInput::Move)
+    }
+}
+
+fn navigate(
+    mut cmd: Commands,
+    mut focus: ResMut<Focus>,
+    mut last_stick_dir: Local<Option<Dir2>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    button_q: Query<(Entity, &GlobalTransform, &ViewVisibility, Has<BackButton>), With<Button>>,
+    mut interaction_q: Query<&mut Interaction>,
+    mut slider_q: Query<&mut Slider>,
+) {
+    let Some(input) = read_nav_input(
+        &keys,
+        &gamepad_buttons,
+        &gamepads,
+        &axes,
+        &mut last_stick_dir,
+    ) else {
+        return;
+    };
+
+    // visible buttons in reading order
+    let mut buttons: Vec<_> = button_q
+        .iter()
+        .filter(|(.., visibility, _)| visibility.get())
+        .map(|(e, t, _, back)| (e, t.translation().truncate(), back))
+        .collect();
+    if buttons.is_empty() {
+        return;
+    }
+    buttons.sort_by(|(_, a, _), (_, b, _)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    if input == NavInput::Back {
+        if let Some((back_e, ..)) = buttons.iter().find(|(.., back)| *back) {
+            press_button(&mut cmd, &mut interaction_q, *back_e);
+        }
+        return;
+    }
+
+    let Some(current) = focus
+        .0
+        .and_then(|focused| buttons.iter().position(|(e, ..)| *e == focused))
+    else {
+        // the first input just shows the focus
+        focus.0 = Some(buttons[0].0);
+        cmd.trigger(PlaySfx::new(SfxKey::ButtonHover));
+        return;
+    };
+    let (focused_e, focused_pos, _) = buttons[current];
+
+    let next = match input {
+        NavInput::Confirm => {
+            // sliders would jump to the cursor when pressed
+            if !slider_q.contains(focused_e) {
+                press_button(&mut cmd, &mut interaction_q, focused_e);
+            }
+            return;
+        }
+        NavInput::Move(dir) if dir.y == 0. && slider_q.contains(focused_e) => {
+            if let Ok(mut slider) = slider_q.get_mut(focused_e) {
+                slider.nudge(dir.x);
+            }
+            return;
+        }
+        NavInput::Move(dir) => buttons
+            .iter()
+            .filter_map(|(e, pos, _)| {
+                let delta = *pos - focused_pos;
+                let along = delta.dot(*dir);
+                // prefer buttons in line with the focused one
+                (along > 1.).then(|| (*e, along + 2. * delta.perp_dot(*dir).abs()))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(e, _)| e),
+        NavInput::Next => Some(buttons[(current + 1) % buttons.len()].0),
+        NavInput::Previous => Some(buttons[(current + buttons.len() - 1) % buttons.len()].0),
+        NavInput::Back => None,
+    };
+
+    if let Some(next) = next {
+        focus.0 = Some(next);
+        cmd.trigger(PlaySfx::new(SfxKey::ButtonHover));
+    }
+}
+
+fn press_button(cmd: &mut Commands, interaction_q: &mut Query<&mut Interaction>, e: Entity) {
+    if let Ok(mut interaction) = interaction_q.get_mut(e) {
+        *interaction = Interaction::Pressed;
+        cmd.insert_resource(NavPress(e));
+    }
+}
@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
 
 pub const NODE_BG: Color = COL_BULLET;
 pub const BUTTON_BG: Color = COL_TRANSITION_1;
@@ -39,3 +42,228 @@ pub const COL_ENEMY_PROJECTILE: Color = Color::srgb(0.92, 0.36, 0.75);
 // and use colors from
 // https://lospec.com/palette-list/chasm
 // for enemies
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Palette>()
+        .add_systems(Update, update_palette.run_if(resource_changed::<Settings>))
+        .add_systems(PostUpdate, (apply_tints, apply_clear_color));
+}
+
+/// The built-in palettes of the game world, the UI keeps its theme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaletteKind {
+    #[default]
+    Citrink,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+    HighContrast,
+}
+
+impl PaletteKind {
+    pub const ALL: [PaletteKind; 5] = [
+        PaletteKind::Citrink,
+        PaletteKind::Deuteranopia,
+        PaletteKind::Protanopia,
+        PaletteKind::Tritanopia,
+        PaletteKind::HighContrast,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PaletteKind::Citrink => "CITRINK",
+            PaletteKind::Deuteranopia => "DEUTERANOPIA",
+            PaletteKind::Protanopia => "PROTANOPIA",
+            PaletteKind::Tritanopia => "TRITANOPIA",
+            PaletteKind::HighContrast => "HIGH CONTRAST",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|kind| *kind == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// The game world colours, rebuilt from [`Settings`] whenever the palette setting changes.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Palette {
+    pub bg: Color,
+    pub paddle: Color,
+    pub paddle_tracks: Color,
+    pub paddle_reflect: Color,
+    pub paddle_capture: Color,
+    pub paddle_captured: Color,
+    pub bullet: Color,
+    pub ball: Color,
+    pub ball_fast: Color,
+    pub gears: Color,
+    pub gears_disabled: Color,
+    pub ammo_bg: Color,
+    pub ammo_fill: Color,
+    pub ammo_out: Color,
+    pub enemy: Color,
+    pub enemy_flash: Color,
+    pub enemy_projectile: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new(default())
+    }
+}
+
+impl Palette {
+    const CITRINK: Palette = Palette {
+        bg: COL_BG,
+        paddle: COL_PADDLE,
+        paddle_tracks: COL_PADDLE_TRACKS,
+        paddle_reflect: COL_PADDLE_REFLECT,
+        paddle_capture: COL_PADDLE_CAPTURE,
+        paddle_captured: COL_PADDLE_CAPTURED,
+        bullet: COL_BULLET,
+        ball: COL_BALL,
+        ball_fast: COL_BALL_FAST,
+        gears: COL_GEARS,
+        gears_disabled: COL_GEARS_DISABLED,
+        ammo_bg: COL_AMMO_BG,
+        ammo_fill: COL_AMMO_FILL,
+        ammo_out: COL_AMMO_OUT,
+        enemy: COL_ENEMY,
+        enemy_flash: COL_ENEMY_FLASH,
+        enemy_projectile: COL_ENEMY_PROJECTILE,
+    };
+
+    pub fn new(kind: PaletteKind) -> Self {
+        // the red-green ones keep the ball, capture & enemies apart by blue vs orange,
+        // https://jfly.uni-koeln.de/color/
+        match kind {
+            PaletteKind::Citrink => Self::CITRINK,
+            PaletteKind::Deuteranopia => Self {
+                paddle_reflect: Color::srgb(0.0, 0.45, 0.70),
+                paddle_capture: Color::srgb(0.34, 0.71, 0.91),
+                bullet: Color::srgb(0.94, 0.89, 0.26),
+                ball: Color::srgb(0.34, 0.71, 0.91),
+                ammo_fill: Color::srgb(0.94, 0.89, 0.26),
+                enemy: Color::srgb(0.90, 0.62, 0.0),
+                enemy_projectile: Color::srgb(0.84, 0.37, 0.0),
+                ..Self::CITRINK
+            },
+            // reds look dark, so the enemies lean on yellow
+            PaletteKind::Protanopia => Self {
+                paddle_reflect: Color::srgb(0.0, 0.45, 0.70),
+                paddle_capture: Color::srgb(0.34, 0.71, 0.91),
+                bullet: Color::srgb(0.99, 0.96, 0.60),
+                ball: Color::srgb(0.34, 0.71, 0.91),
+                ammo_fill: Color::srgb(0.99, 0.96, 0.60),
+                enemy: Color::srgb(0.95, 0.75, 0.10),
+                enemy_projectile: Color::srgb(0.95, 0.60, 0.0),
+                ..Self::CITRINK
+            },
+            // blue & yellow get confused, red vs cyan stays apart
+            PaletteKind::Tritanopia => Self {
+                paddle: Color::srgb(0.40, 0.60, 0.60),
+                paddle_reflect: Color::srgb(0.0, 0.50, 0.55),
+                paddle_capture: Color::srgb(0.30, 0.85, 0.85),
+                bullet: Color::srgb(0.99, 0.80, 0.85),
+                ball: Color::srgb(0.30, 0.85, 0.85),
+                ammo_fill: Color::srgb(0.99, 0.80, 0.85),
+                enemy: Color::srgb(0.86, 0.20, 0.30),
+                enemy_projectile: Color::srgb(0.95, 0.35, 0.45),
+                ..Self::CITRINK
+            },
+            PaletteKind::HighContrast => Self {
+                bg: Color::BLACK,
+                paddle: Color::WHITE,
+                paddle_tracks: Color::srgb(0.5, 0.5, 0.5),
+                paddle_reflect: Color::srgb(0.0, 0.6, 1.0),
+                paddle_capture: Color::srgb(1.0, 1.0, 0.0),
+                paddle_captured: Color::srgb(0.4, 0.4, 0.4),
+                bullet: Color::srgb(1.0, 0.95, 0.2),
+                ball: Color::srgb(0.0, 1.0, 1.0),
+                ball_fast: Color::WHITE,
+                gears: Color::srgb(0.45, 0.45, 0.45),
+                gears_disabled: Color::srgb(0.2, 0.2, 0.2),
+                ammo_bg: Color::srgb(0.45, 0.45, 0.45),
+                ammo_fill: Color::srgb(1.0, 0.95, 0.2),
+                ammo_out: Color::srgb(0.2, 0.2, 0.2),
+                enemy: Color::srgb(1.0, 0.25, 0.25),
+                enemy_flash: Color::WHITE,
+                enemy_projectile: Color::srgb(1.0, 0.5, 0.0),
+            },
+        }
+    }
+
+    pub fn get(&self, tint: Tint) -> Color {
+        match tint {
+            Tint::Bg => self.bg,
+            Tint::Paddle => self.paddle,
+            Tint::PaddleTracks => self.paddle_tracks,
+            Tint::Bullet => self.bullet,
+            Tint::Gears => self.gears,
+            Tint::GearsDisabled => self.gears_disabled,
+            Tint::AmmoBg => self.ammo_bg,
+            Tint::AmmoFill => self.ammo_fill,
+            Tint::AmmoOut => self.ammo_out,
+            Tint::Enemy => self.enemy,
+        }
+    }
+}
+
+/// Keeps the colour of a sprite, colour material, UI node or image in sync with the [`Palette`].
+/// The alpha is left alone so fades keep working. Swapping the tint doesn't re-colour
+/// right away, so it can be tweened to.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tint {
+    Bg,
+    Paddle,
+    PaddleTracks,
+    Bullet,
+    Gears,
+    GearsDisabled,
+    AmmoBg,
+    AmmoFill,
+    AmmoOut,
+    Enemy,
+}
+
+fn update_palette(settings: Res<Settings>, mut palette: ResMut<Palette>) {
+    palette.set_if_neq(Palette::new(settings.accessibility.palette));
+}
+
+fn apply_tints(
+    palette: Res<Palette>,
+    mut tint_q: Query<(
+        Ref<Tint>,
+        Option<&mut Sprite>,
+        Option<&Handle<ColorMaterial>>,
+        Option<&mut BackgroundColor>,
+        Option<&mut UiImage>,
+    )>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (tint, sprite, material, bg, image) in &mut tint_q {
+        if !palette.is_changed() && !tint.is_added() {
+            continue;
+        }
+        let color = palette.get(*tint);
+        if let Some(mut sprite) = sprite {
+            sprite.color = color.with_alpha(sprite.color.alpha());
+        }
+        if let Some(material) = material.and_then(|handle| materials.get_mut(handle)) {
+            material.color = color.with_alpha(material.color.alpha());
+        }
+        if let Some(mut bg) = bg {
+            bg.0 = color.with_alpha(bg.0.alpha());
+        }
+        if let Some(mut image) = image {
+            image.color = color.with_alpha(image.color.alpha());
+        }
+    }
+}
+
+fn apply_clear_color(palette: Res<Palette>, mut clear_color: ResMut<ClearColor>) {
+    if palette.is_changed() {
+        clear_color.0 = palette.bg;
+    }
+}
@@ -1,9 +1,102 @@
 //! Helper traits for creating common widgets.
 
-use bevy::{ecs::system::EntityCommands, prelude::*, ui::Val::*};
+use bevy::{
+    ecs::system::EntityCommands,
+    prelude::*,
+    ui::{RelativeCursorPosition, Val::*},
+};
 
 use super::{interaction::InteractionPalette, palette::*};
 
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            (drag_slider, update_slider).chain(),
+            (press_toggle, update_toggle).chain(),
+        ),
+    );
+}
+
+/// Value of a [`Widgets::slider`], set by clicking or dragging along it.
+#[derive(Component, Debug, Clone)]
+pub struct Slider {
+    pub value: f32,
+    min: f32,
+    max: f32,
+    step: f32,
+    caption: String,
+    format: SliderFormat,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SliderFormat {
+    Number,
+    /// Shows the value times 100 with a `%` sign.
+    Percent,
+}
+
+impl Slider {
+    pub fn new(caption: impl Into<String>, value: f32, min: f32, max: f32) -> Self {
+        Self {
+            value: value.clamp(min, max),
+            min,
+            max,
+            step: 0.,
+            caption: caption.into(),
+            format: SliderFormat::Number,
+        }
+    }
+
+    /// Rounds the value to multiples of the step.
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn with_format(mut self, format: SliderFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Moves the value by steps, a twentieth of the range without a step.
+    pub(super) fn nudge(&mut self, steps: f32) {
+        let step = if self.step > 0. {
+            self.step
+        } else {
+            (self.max - self.min) / 20.
+        };
+        self.value = (self.value + steps * step).clamp(self.min, self.max);
+    }
+
+    fn fraction(&self) -> f32 {
+        ((self.value - self.min) / (self.max - self.min)).clamp(0., 1.)
+    }
+
+    fn text(&self) -> String {
+        match self.format {
+            SliderFormat::Number => format!("{}: {:.0}", self.caption, self.value),
+            SliderFormat::Percent => format!("{}: {:.0}%", self.caption, self.value * 100.),
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+struct SliderFill;
+
+/// State of a [`Widgets::toggle`], flipped by pressing it.
+#[derive(Component, Debug, Clone)]
+pub struct Toggle {
+    pub on: bool,
+    caption: String,
+}
+
+impl Toggle {
+    fn text(&self) -> String {
+        format!("{}: {}", self.caption, if self.on { "ON" } else { "OFF" })
+    }
+}
+
 /// An extension trait for spawning UI widgets.
 pub trait Widgets {
     /// Spawn a simple button with text.
@@ -14,6 +107,16 @@ pub trait Widgets {
 
     /// Spawn a simple text label.
     fn label(&mut self, text: impl Into<String>) -> EntityCommands;
+
+    /// Spawn a slider, the [`Slider`] is on the returned entity.
+    fn slider(&mut self, slider: Slider) -> EntityCommands;
+
+    /// Spawn an on/off button, the [`Toggle`] is on the returned entity.
+    fn toggle(&mut self, caption: impl Into<String>, on: bool) -> EntityCommands;
+
+    /// Spawn a HUD readout: a small caption above a bigger value.
+    /// The value is the second section of the spawned [`Text`].
+    fn readout(&mut self, caption: impl Into<String>, value: impl Into<String>) -> EntityCommands;
 }
 
 impl<T: Spawn> Widgets for T {
@@ -22,8 +125,9 @@ impl<T: Spawn> Widgets for T {
             Name::new("Button"),
             ButtonBundle {
                 style: Style {
-                    width: Px(200.0),
+                    min_width: Px(200.0),
                     height: Px(65.0),
+                    padding: UiRect::horizontal(Px(20.0)),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
@@ -115,6 +219,99 @@ impl<T: Spawn> Widgets for T {
         });
         entity
     }
+
+    fn slider(&mut self, slider: Slider) -> EntityCommands {
+        let fraction = slider.fraction();
+        let text = slider.text();
+        let mut entity = self.spawn((
+            Name::new("Slider"),
+            ButtonBundle {
+                style: Style {
+                    width: Px(420.0),
+                    height: Px(50.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                background_color: BackgroundColor(BUTTON_PRESSED_BG),
+                border_radius: BorderRadius::all(Val::Px(12.)),
+                ..default()
+            },
+            InteractionPalette {
+                none: BUTTON_PRESSED_BG,
+                hovered: BUTTON_HOVERED_BG,
+                pressed: BUTTON_HOVERED_BG,
+            },
+            RelativeCursorPosition::default(),
+            slider,
+        ));
+        entity.with_children(|children| {
+            children.spawn((
+                Name::new("Slider Fill"),
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Px(0.0),
+                        width: Percent(fraction * 100.0),
+                        height: Percent(100.0),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(BUTTON_BG),
+                    ..default()
+                },
+                SliderFill,
+            ));
+            children.spawn((
+                Name::new("Slider Text"),
+                TextBundle::from_section(
+                    text,
+                    TextStyle {
+                        font_size: 32.0,
+                        color: BUTTON_TEXT,
+                        ..default()
+                    },
+                ),
+            ));
+        });
+        entity
+    }
+
+    fn toggle(&mut self, caption: impl Into<String>, on: bool) -> EntityCommands {
+        let toggle = Toggle {
+            on,
+            caption: caption.into(),
+        };
+        let mut entity = self.button(toggle.text());
+        entity.insert(toggle);
+        entity
+    }
+
+    fn readout(&mut self, caption: impl Into<String>, value: impl Into<String>) -> EntityCommands {
+        let caption: String = caption.into();
+        self.spawn((
+            Name::new("Readout"),
+            TextBundle::from_sections([
+                TextSection::new(
+                    caption + "\n",
+                    TextStyle {
+                        font_size: 20.0,
+                        color: LABEL_TEXT,
+                        ..default()
+                    },
+                ),
+                TextSection::new(
+                    value,
+                    TextStyle {
+                        font_size: 36.0,
+                        color: LABEL_TEXT,
+                        ..default()
+                    },
+                ),
+            ])
+            .with_text_justify(JustifyText::Center),
+        ))
+    }
 }
 
 /// An extension trait for spawning UI containers.
@@ -164,3 +361,60 @@ impl Spawn for ChildBuilder<'_> {
         self.spawn(bundle)
     }
 }
+
+fn drag_slider(mut slider_q: Query<(&Interaction, &RelativeCursorPosition, &mut Slider)>) {
+    for (interaction, cursor, mut slider) in &mut slider_q {
+        let Some(cursor) = cursor.normalized else {
+            continue;
+        };
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let mut value = slider.min + cursor.x.clamp(0., 1.) * (slider.max - slider.min);
+        if slider.step > 0. {
+            value = (value / slider.step).round() * slider.step;
+        }
+        let value = value.clamp(slider.min, slider.max);
+        if slider.value != value {
+            slider.value = value;
+        }
+    }
+}
+
+fn update_slider(
+    slider_q: Query<(&Slider, &Children), Changed<Slider>>,
+    mut fill_q: Query<&mut Style, With<SliderFill>>,
+    mut text_q: Query<&mut Text>,
+) {
+    for (slider, children) in &slider_q {
+        for child in children {
+            if let Ok(mut style) = fill_q.get_mut(*child) {
+                style.width = Percent(slider.fraction() * 100.0);
+            }
+            if let Ok(mut text) = text_q.get_mut(*child) {
+                text.sections[0].value = slider.text();
+            }
+        }
+    }
+}
+
+fn press_toggle(mut toggle_q: Query<(&Interaction, &mut Toggle), Changed<Interaction>>) {
+    for (interaction, mut toggle) in &mut toggle_q {
+        if *interaction == Interaction::Pressed {
+            toggle.on = !toggle.on;
+        }
+    }
+}
+
+fn update_toggle(
+    toggle_q: Query<(&Toggle, &Children), Changed<Toggle>>,
+    mut text_q: Query<&mut Text>,
+) {
+    for (toggle, children) in &toggle_q {
+        let mut texts = text_q.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.sections[0].value = toggle.text();
+        }
+    }
+}